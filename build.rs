@@ -0,0 +1,137 @@
+//! Generates a test for each line of `tests/examples.txt`, which the harness in
+//! `tests/examples.rs` includes.
+
+use std::{collections::HashSet, env, fmt::Write, fs, path::Path};
+
+const MANIFEST: &str = "tests/examples.txt";
+const EXAMPLES: &[&str] = &["Parse", "Part1", "Part2", "Other"];
+
+fn main() {
+    println!("cargo:rerun-if-changed={MANIFEST}");
+
+    let manifest = fs::read_to_string(MANIFEST).expect("failed to read the example manifest");
+    let mut tests = String::new();
+    let mut names = HashSet::new();
+
+    for (idx, line) in manifest.lines().enumerate() {
+        let line_num = idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fail = |msg: &str| -> ! { panic!("{MANIFEST}:{line_num}: {msg}") };
+
+        let mut fields = line.split_whitespace();
+        let (Some(day), Some(example), Some(index), Some(part1), Some(part2)) = (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        ) else {
+            fail("expected `day example index part1 part2 [variant] [name=value...]`");
+        };
+
+        // Parameters come last, and variant names never contain `=`.
+        let rest: Vec<_> = fields.collect();
+        let variant_len = rest.iter().take_while(|f| !f.contains('=')).count();
+        let variant = rest[..variant_len].join(" ");
+        let mut case = None;
+        let mut params = Vec::new();
+        for param in &rest[variant_len..] {
+            let Some((name, value)) = param.split_once('=') else {
+                fail("parameters should come after the variant");
+            };
+            let Ok(value) = value.parse::<i64>() else {
+                fail(&format!("the value of `{name}` should be a number"))
+            };
+            match name {
+                "case" if value >= 1 => case = Some(value as usize),
+                "case" => fail("cases are numbered from 1"),
+                _ => params.push((name, value)),
+            }
+        }
+
+        let Ok(day) = day.parse::<u8>() else {
+            fail("the day should be a number")
+        };
+        if !EXAMPLES.contains(&example) {
+            fail(&format!("the example should be one of {EXAMPLES:?}"));
+        }
+        let Ok(index) = index.parse::<u8>() else {
+            fail("the example index should be a number")
+        };
+
+        let mut name = format!("day{day:02}_{}_{index}", example.to_lowercase());
+        if !variant.is_empty() {
+            name.push('_');
+            name.extend(variant.chars().map(|c| match c {
+                'a'..='z' | '0'..='9' => c,
+                'A'..='Z' => c.to_ascii_lowercase(),
+                _ => '_',
+            }));
+        }
+        if let Some(case) = case {
+            write!(name, "_case{case}").unwrap();
+        }
+        if !names.insert(name.clone()) {
+            fail("duplicate entry");
+        }
+
+        let answer = |answer: &str| match answer {
+            "-" => "None".to_owned(),
+            _ => format!("Some({:?})", unescape(answer)),
+        };
+        let variant = match variant.as_str() {
+            "" => "None".to_owned(),
+            _ => format!("Some({variant:?})"),
+        };
+
+        writeln!(
+            tests,
+            "#[test]
+fn {name}() {{
+    check(&Entry {{
+        line: {line_num},
+        day: {day},
+        example: Example::{example},
+        index: {index},
+        part1: {},
+        part2: {},
+        variant: {variant},
+        case: {case:?},
+        params: &{params:?},
+    }});
+}}
+",
+            answer(part1),
+            answer(part2),
+        )
+        .unwrap();
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("examples.rs"), tests).unwrap();
+}
+
+/// Undoes the escaping of answers which span several lines, as in the answer store.
+fn unescape(answer: &str) -> String {
+    let mut unescaped = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
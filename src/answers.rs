@@ -0,0 +1,163 @@
+//! A store of known answers to the real puzzle inputs, so refactors can be checked against them.
+//!
+//! The store is a text file with one answer per line, keyed by year, day and part:
+//!
+//! ```text
+//! # year day part answer
+//! 2022 1 1 71502
+//! 2022 25 1 2=-1=0
+//! ```
+//!
+//! Answers which span several lines, such as the drawn letters on day 10, have their newlines
+//! written as `\n`, and backslashes as `\\`.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs, io,
+    path::Path,
+};
+
+use color_eyre::eyre::{eyre, WrapErr};
+
+use crate::{solution::Part, DayError};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnswerStore {
+    answers: BTreeMap<(u16, u8, Part), String>,
+}
+
+impl AnswerStore {
+    /// Loads the store from `path`, which is empty if the file doesn't exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, DayError> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(text) => text
+                .parse()
+                .wrap_err_with(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), DayError> {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(String::as_str)
+    }
+
+    /// Stores the answer, replacing any answer already stored for the part.
+    pub fn insert(&mut self, year: u16, day: u8, part: Part, answer: impl Into<String>) {
+        self.answers.insert((year, day, part), answer.into());
+    }
+}
+
+impl std::str::FromStr for AnswerStore {
+    type Err = DayError;
+
+    fn from_str(text: &str) -> Result<Self, DayError> {
+        let mut store = Self::default();
+
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(4, ' ');
+            let (Some(year), Some(day), Some(part), Some(answer)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(eyre!("Line {}: expected `year day part answer`", idx + 1));
+            };
+
+            let part = match part {
+                "1" => Part::One,
+                "2" => Part::Two,
+                _ => return Err(eyre!("Line {}: part should be 1 or 2", idx + 1)),
+            };
+            let year = year.parse().wrap_err_with(|| format!("Line {}", idx + 1))?;
+            let day = day.parse().wrap_err_with(|| format!("Line {}", idx + 1))?;
+
+            store.insert(year, day, part, unescape(answer));
+        }
+
+        Ok(store)
+    }
+}
+
+impl Display for AnswerStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# year day part answer")?;
+        for (&(year, day, part), answer) in &self.answers {
+            let part = match part {
+                Part::One => 1,
+                Part::Two => 2,
+            };
+            writeln!(f, "{year} {day} {part} {}", escape(answer))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(answer: &str) -> String {
+    let mut unescaped = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let mut store = AnswerStore::default();
+        store.insert(2022, 25, Part::One, "2=-1=0");
+        store.insert(2022, 10, Part::Two, "#..#\n#\\.#");
+        store.insert(2022, 1, Part::One, "24000");
+
+        let text = store.to_string();
+        assert_eq!(
+            "# year day part answer\n\
+             2022 1 1 24000\n\
+             2022 10 2 #..#\\n#\\\\.#\n\
+             2022 25 1 2=-1=0\n",
+            text
+        );
+
+        let parsed: AnswerStore = text.parse().unwrap();
+        assert_eq!(store, parsed);
+        assert_eq!(Some("#..#\n#\\.#"), parsed.get(2022, 10, Part::Two));
+        assert_eq!(None, parsed.get(2022, 10, Part::One));
+    }
+
+    #[test]
+    fn invalid_test() {
+        assert!("2022 1 3 100".parse::<AnswerStore>().is_err());
+        assert!("2022 x 1 100".parse::<AnswerStore>().is_err());
+        assert!("2022 1 1".parse::<AnswerStore>().is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Allocation budgets, for checking that solvers which promise not to allocate keep to it.
+//!
+//! [`CountingAlloc`] wraps the global allocator and counts the allocations made by each thread
+//! while it's measuring. The library's own tests install it around `aoc_lib::TracingAlloc`, so a
+//! day's tests can wrap a solver in [`assert_within`].
+
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    cell::Cell,
+    fmt::{self, Display},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The heap usage of a closure run by [`measure`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// Allocations and reallocations made.
+    pub allocations: usize,
+    /// The most bytes held at once, beyond what was held when the closure started.
+    pub peak_bytes: usize,
+}
+
+/// The most heap a solver may use.
+pub type Budget = Usage;
+
+impl Usage {
+    pub const NONE: Self = Self {
+        allocations: 0,
+        peak_bytes: 0,
+    };
+
+    pub fn within(&self, budget: &Budget) -> bool {
+        self.allocations <= budget.allocations && self.peak_bytes <= budget.peak_bytes
+    }
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations peaking at {} bytes",
+            self.allocations, self.peak_bytes
+        )
+    }
+}
+
+/// Set once [`CountingAlloc`] has been used, so [`measure`] can tell it's been installed.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static MEASURING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static CURRENT_BYTES: Cell<isize> = const { Cell::new(0) };
+    static PEAK_BYTES: Cell<isize> = const { Cell::new(0) };
+}
+
+/// A global allocator which counts the allocations made inside [`measure`], and passes them all
+/// on to the allocator it wraps.
+pub struct CountingAlloc<A>(pub A);
+
+impl<A> CountingAlloc<A> {
+    fn record(&self, allocations: usize, bytes: isize) {
+        INSTALLED.store(true, Ordering::Relaxed);
+
+        // The thread locals may already be gone while the thread is shutting down.
+        let _ = MEASURING.try_with(|measuring| {
+            if !measuring.get() {
+                return;
+            }
+
+            ALLOCATIONS.set(ALLOCATIONS.get() + allocations);
+            let current = CURRENT_BYTES.get() + bytes;
+            CURRENT_BYTES.set(current);
+            PEAK_BYTES.set(PEAK_BYTES.get().max(current));
+        });
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAlloc<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.record(1, layout.size() as isize);
+        self.0.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        self.record(1, layout.size() as isize);
+        self.0.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.record(0, -(layout.size() as isize));
+        self.0.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        self.record(1, new_size as isize - layout.size() as isize);
+        self.0.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Stops [`measure`] counting when dropped, so a closure which panics doesn't leave its thread
+/// counting every allocation after it.
+struct StopMeasuring;
+
+impl Drop for StopMeasuring {
+    fn drop(&mut self) {
+        MEASURING.set(false);
+    }
+}
+
+/// Runs `f`, and counts the heap it uses on this thread. Allocations made by threads it spawns
+/// aren't counted.
+///
+/// Panics if [`CountingAlloc`] isn't the global allocator, as nothing would be counted.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Usage) {
+    // Anything allocating at all before now shows the allocator is installed.
+    drop(Box::new(0u8));
+    assert!(
+        INSTALLED.load(Ordering::Relaxed),
+        "CountingAlloc must be the global allocator to measure allocations"
+    );
+    assert!(!MEASURING.get(), "measure can't be nested");
+
+    ALLOCATIONS.set(0);
+    CURRENT_BYTES.set(0);
+    PEAK_BYTES.set(0);
+    MEASURING.set(true);
+    let stop = StopMeasuring;
+    let result = f();
+    drop(stop);
+
+    let usage = Usage {
+        allocations: ALLOCATIONS.get(),
+        peak_bytes: PEAK_BYTES.get() as usize,
+    };
+    (result, usage)
+}
+
+/// Runs `f`, and panics if it uses more heap than `budget` allows.
+#[track_caller]
+pub fn assert_within<R>(budget: Budget, f: impl FnOnce() -> R) -> R {
+    let (result, usage) = measure(f);
+    assert!(
+        usage.within(&budget),
+        "Used {usage}, over the budget of {budget}"
+    );
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measure_test() {
+        let ((), usage) = measure(|| {});
+        assert_eq!(Usage::NONE, usage);
+
+        let (v, usage) = measure(|| {
+            let mut v = Vec::<u64>::with_capacity(4);
+            drop(vec![0u8; 1000]);
+            v.extend([1, 2, 3, 4, 5]);
+            v
+        });
+        assert_eq!(5, v.len());
+        assert_eq!(3, usage.allocations);
+        assert_eq!(1032, usage.peak_bytes);
+    }
+
+    #[test]
+    fn panic_test() {
+        let panicked = std::panic::catch_unwind(|| measure(|| panic!("in measure")));
+        assert!(panicked.is_err());
+
+        // Measuring again would panic as nested if the first had been left running.
+        let ((), usage) = measure(|| {});
+        assert_eq!(Usage::NONE, usage);
+    }
+
+    #[test]
+    #[should_panic = "over the budget"]
+    fn over_budget_test() {
+        assert_within(Budget::NONE, || Box::new(1));
+    }
+}
@@ -1,23 +1,32 @@
 use aoc_lib::Day;
 
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day17;
-mod day21;
-mod day25;
+use crate::solution::{self, CrossCheck, Solver};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
 
 pub static DAYS: &[Day] = &[
     day01::DAY,
@@ -35,7 +44,86 @@ pub static DAYS: &[Day] = &[
     day13::DAY,
     day14::DAY,
     day15::DAY,
+    day16::DAY,
     day17::DAY,
+    day18::DAY,
+    day19::DAY,
+    day20::DAY,
     day21::DAY,
+    day22::DAY,
+    day23::DAY,
+    day24::DAY,
     day25::DAY,
 ];
+
+/// Every day's solvers, for checking their answers without benchmarking them.
+pub static SOLVERS: &[Solver] = &[
+    solution::solver::<day01::Day01>(),
+    solution::solver::<day02::Day02>(),
+    solution::solver::<day03::Day03>(),
+    solution::solver::<day04::Day04>(),
+    solution::solver::<day05::Day05>(),
+    solution::solver::<day06::Day06>(),
+    solution::solver::<day07::Day07>(),
+    solution::solver::<day08::Day08>(),
+    solution::solver::<day09::Day09>(),
+    solution::solver::<day10::Day10>(),
+    solution::solver::<day11::Day11>(),
+    solution::solver::<day12::Day12>(),
+    solution::solver::<day13::Day13>(),
+    solution::solver::<day14::Day14>(),
+    solution::solver::<day15::Day15>(),
+    solution::solver::<day16::Day16>(),
+    solution::solver::<day17::Day17>(),
+    solution::solver::<day18::Day18>(),
+    solution::solver::<day19::Day19>(),
+    solution::solver::<day20::Day20>(),
+    solution::solver::<day21::Day21>(),
+    solution::solver::<day22::Day22>(),
+    solution::solver::<day23::Day23>(),
+    solution::solver::<day24::Day24>(),
+    solution::solver::<day25::Day25>(),
+];
+
+/// The days with alternative solvers to check against the canonical parts.
+pub static CROSS_CHECKS: &[CrossCheck] = &[
+    solution::cross_check::<day01::Day01>(),
+    solution::cross_check::<day02::Day02>(),
+    solution::cross_check::<day04::Day04>(),
+    solution::cross_check::<day19::Day19>(),
+    solution::cross_check::<day20::Day20>(),
+    solution::cross_check::<day25::Day25>(),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn cross_check_examples() {
+        for check in CROSS_CHECKS {
+            let data = aoc_lib::input(check.day)
+                .example(Example::Part1, 1)
+                .open()
+                .unwrap();
+
+            let mismatches = (check.run)(&data).unwrap();
+            assert!(mismatches.is_empty(), "{:?}", mismatches);
+        }
+    }
+
+    #[test]
+    fn cross_check_windows_examples() {
+        for check in CROSS_CHECKS {
+            let data = aoc_lib::input(check.day)
+                .example(Example::Part1, 1)
+                .open()
+                .unwrap();
+            let data = format!("\u{feff}{}", data.trim_end().replace('\n', "\r\n"));
+
+            let mismatches = (check.run)(&data).unwrap();
+            assert!(mismatches.is_empty(), "{:?}", mismatches);
+        }
+    }
+}
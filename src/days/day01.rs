@@ -1,44 +1,71 @@
-use aoc_lib::{misc::Top, Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{Report, Result};
+use std::io::BufRead;
+
+use aoc_lib::{misc::Top, Bench, BenchResult, Day, NoError, UserError};
+
+use crate::{
+    input::{self, parse_at},
+    solution::{self, run_parse, Part, Runner, Solution, Variant},
+    DayError,
+};
 
 // 11:43
 // 12:01
 
-pub const DAY: Day = Day {
-    day: 1,
-    name: "Calorie Counting",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[("Parse", run_parse), ("No Alloc", run_no_alloc)],
-};
+pub const DAY: Day = solution::day::<Day01>();
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(solve::<1>(&data)))
-}
+pub struct Day01;
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(solve::<3>(&data)))
-}
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const NAME: &'static str = "Calorie Counting";
+    const OTHER: &'static [(&'static str, Runner)] =
+        &[("Parse", run_parse::<Self>), ("No Alloc", run_no_alloc)];
+    const VARIANTS: &'static [Variant] = &[Variant {
+        name: "No Alloc",
+        part: Part::Two,
+        solve: |input| Ok(no_alloc_solve(input).to_string()),
+    }];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<u32, DayError> {
+        Ok(part1(data))
+    }
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
+    fn part2(data: &Parsed) -> Result<u32, DayError> {
+        Ok(part2(data))
+    }
 }
 
 fn run_no_alloc(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| Ok::<_, NoError>(no_alloc_solve(input)))
+    let input = solution::normalise::<Day01>(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(no_alloc_solve(&input)))
 }
 
-fn parse(input: &str) -> Result<Vec<Vec<u32>>, std::num::ParseIntError> {
-    input
+pub type Parsed = Vec<Vec<u32>>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    let elves = input
         .trim()
         .split("\n\n")
         .map(|g| g.trim().lines().map(str::parse).collect())
-        .collect()
+        .collect::<Result<_, _>>()?;
+
+    Ok(elves)
+}
+
+pub fn part1(elves: &[Vec<u32>]) -> u32 {
+    solve::<1>(elves)
+}
+
+pub fn part2(elves: &[Vec<u32>]) -> u32 {
+    solve::<3>(elves)
 }
 
 fn solve<const N: usize>(elves: &[Vec<u32>]) -> u32 {
@@ -50,6 +77,29 @@ fn solve<const N: usize>(elves: &[Vec<u32>]) -> u32 {
     leaders.0.into_iter().sum()
 }
 
+/// Solves both parts from a reader, holding only the current elf's total and the top three.
+///
+/// The totals are `u64`s, as an input too large to fit in memory can easily pass `u32::MAX`.
+pub fn stream(reader: impl BufRead) -> Result<(u64, u64), DayError> {
+    let mut leaders = Top([0; 3]);
+    let mut sum = 0;
+    input::for_each_line(reader, |line| {
+        if line.is_empty() {
+            leaders.push(sum);
+            sum = 0;
+        } else {
+            sum += u64::from(parse_at::<u32>(line, line, "a number of calories")?);
+        }
+        Ok(())
+    })?;
+    leaders.push(sum);
+
+    let most = leaders.0.into_iter().max().unwrap_or(0);
+    Ok((most, leaders.0.into_iter().sum()))
+}
+
+/// Solves part 2 in a single pass over the bytes. Any byte other than a newline is taken to be a
+/// digit, so this needs normalised input.
 fn no_alloc_solve(input: &str) -> u32 {
     let mut leaders = Top([0; 3]);
 
@@ -88,48 +138,53 @@ fn no_alloc_solve(input: &str) -> u32 {
 #[cfg(test)]
 mod day01_tests {
     use super::*;
+    use crate::budget::{self, Budget};
     use aoc_lib::Example;
+    use proptest::prelude::*;
 
     #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
+    fn no_alloc_budget_test() {
+        let input = aoc_lib::input(DAY.day)
             .example(Example::Part1, 1)
             .open()
             .unwrap();
 
-        let data = parse(&data).unwrap();
-
-        let expected = 24000;
-        let actual = solve::<1>(&data);
-
-        assert_eq!(expected, actual);
+        let answer = budget::assert_within(Budget::NONE, || no_alloc_solve(&input));
+        assert_eq!(45000, answer);
     }
 
-    #[test]
-    fn part2_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let data = parse(&data).unwrap();
+    fn input() -> impl Strategy<Value = String> {
+        let elf = prop::collection::vec(1..100_000u32, 1..20);
+        prop::collection::vec(elf, 1..50).prop_map(|elves| {
+            let elves: Vec<String> = elves
+                .iter()
+                .map(|e| e.iter().map(|c| format!("{c}\n")).collect())
+                .collect();
+            elves.join("\n")
+        })
+    }
 
-        let expected = 45000;
-        let actual = solve::<3>(&data);
+    proptest! {
+        #[test]
+        fn no_alloc_matches_parsed(input in input()) {
+            let elves = parse(&input).unwrap();
+            prop_assert_eq!(part2(&elves), no_alloc_solve(&input));
+        }
 
-        assert_eq!(expected, actual);
+        #[test]
+        fn stream_matches_parsed(input in input()) {
+            let elves = parse(&input).unwrap();
+            let expected = (u64::from(part1(&elves)), u64::from(part2(&elves)));
+            prop_assert_eq!(expected, stream(input.as_bytes()).unwrap());
+        }
     }
 
     #[test]
-    fn part2_no_alloc_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let expected = 45000;
-        let actual = no_alloc_solve(&data);
-
-        assert_eq!(expected, actual);
+    fn stream_past_u32_test() {
+        let input = "4000000000\n4000000000\n\n1\n\n3000000000\n";
+        assert_eq!(
+            (8_000_000_000, 11_000_000_001),
+            stream(input.as_bytes()).unwrap()
+        );
     }
 }
@@ -1,49 +1,81 @@
-use aoc_lib::{misc::ArrChunks, Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{eyre::eyre, Report, Result};
-
-pub const DAY: Day = Day {
-    day: 2,
-    name: "Rock Paper Scissors",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[
+use std::io::BufRead;
+
+use aoc_lib::{misc::ArrChunks, Bench, BenchResult, Day, ParseResult, UserError};
+use color_eyre::{Report, Result};
+
+use crate::{
+    input::{self, ParseError},
+    solution::{self, Part, Runner, Solution, Variant},
+    DayError,
+};
+
+pub const DAY: Day = solution::day::<Day02>();
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const NAME: &'static str = "Rock Paper Scissors";
+    const OTHER: &'static [(&'static str, Runner)] = &[
         ("Parse Part 1", run_parse_part_1),
         ("Parse Part 2", run_parse_part_2),
         ("Part 1 Fast", run_part1_fast),
         ("Part 2 Fast", run_part2_fast),
-    ],
-};
+    ];
+    const VARIANTS: &'static [Variant] = &[
+        Variant {
+            name: "Part 1 Fast",
+            part: Part::One,
+            solve: |input| Ok(part1_no_alloc(input)?.to_string()),
+        },
+        Variant {
+            name: "Part 2 Fast",
+            part: Part::Two,
+            solve: |input| Ok(part2_no_alloc(input)?.to_string()),
+        },
+    ];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse_part1(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1(&data)))
-}
+    fn part1(data: &Parsed) -> Result<u32, DayError> {
+        Ok(part1(data))
+    }
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let data = parse_part2(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1(&data)))
+    fn part2(data: &Parsed) -> Result<u32, DayError> {
+        Ok(part2(data))
+    }
 }
 
 fn run_parse_part_1(input: &str, b: Bench) -> BenchResult {
+    let input = solution::normalise::<Day02>(input).map_err(UserError)?;
     b.bench(|| {
-        let data = parse_part1(input).map_err(UserError)?;
+        let data = parse_part1(&input).map_err(UserError)?;
         Ok::<_, Report>(ParseResult(data))
     })
 }
 
 fn run_parse_part_2(input: &str, b: Bench) -> BenchResult {
+    let input = solution::normalise::<Day02>(input).map_err(UserError)?;
     b.bench(|| {
-        let data = parse_part2(input).map_err(UserError)?;
+        let data = parse_part2(&input).map_err(UserError)?;
         Ok::<_, Report>(ParseResult(data))
     })
 }
 
 fn run_part1_fast(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| Ok::<_, NoError>(part1_no_alloc(input)))
+    let input = solution::normalise::<Day02>(input).map_err(UserError)?;
+    b.bench(|| part1_no_alloc(&input))
 }
 
 fn run_part2_fast(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| Ok::<_, NoError>(part2_no_alloc(input)))
+    let input = solution::normalise::<Day02>(input).map_err(UserError)?;
+    b.bench(|| part2_no_alloc(&input))
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -83,73 +115,117 @@ impl Play {
 }
 
 fn parse_part1(input: &str) -> Result<Vec<Play>> {
-    let mut plays = Vec::new();
-
-    for line in input.lines() {
-        let Some((a, b)) = line.trim().split_once(' ') else {return Err(eyre!("Invalid character"))};
-
-        use Hand::*;
-        let player_one = match a {
-            "A" => Rock,
-            "B" => Paper,
-            "C" => Scissors,
-            _ => return Err(eyre!("Invalid character")),
-        };
-
-        let player_two = match b {
-            "X" => Rock,
-            "Y" => Paper,
-            "Z" => Scissors,
-            _ => return Err(eyre!("Invalid character")),
-        };
-
-        plays.push(Play {
-            player_one,
-            player_two,
-        });
-    }
+    input
+        .lines()
+        .map(|line| Ok(parse_play_part1(input, line)?))
+        .collect()
+}
 
-    Ok(plays)
+fn parse_play_part1(input: &str, line: &str) -> Result<Play, ParseError> {
+    let Some((a, b)) = line.trim().split_once(' ') else {
+        return Err(ParseError::at(input, line, "a round like `A Y`"));
+    };
+
+    use Hand::*;
+    let player_one = match a {
+        "A" => Rock,
+        "B" => Paper,
+        "C" => Scissors,
+        _ => return Err(ParseError::at(input, a, "`A`, `B` or `C`")),
+    };
+
+    let player_two = match b {
+        "X" => Rock,
+        "Y" => Paper,
+        "Z" => Scissors,
+        _ => return Err(ParseError::at(input, b, "`X`, `Y` or `Z`")),
+    };
+
+    Ok(Play {
+        player_one,
+        player_two,
+    })
 }
 
 fn parse_part2(input: &str) -> Result<Vec<Play>> {
-    let mut plays = Vec::new();
+    input
+        .lines()
+        .map(|line| Ok(parse_play_part2(input, line)?))
+        .collect()
+}
 
-    for line in input.lines() {
-        let Some((a, b)) = line.trim().split_once(' ') else {return Err(eyre!("Invalid character"))};
+fn parse_play_part2(input: &str, line: &str) -> Result<Play, ParseError> {
+    let Some((a, b)) = line.trim().split_once(' ') else {
+        return Err(ParseError::at(input, line, "a round like `A Y`"));
+    };
+
+    use Hand::*;
+    let player_one = match a {
+        "A" => Rock,
+        "B" => Paper,
+        "C" => Scissors,
+        _ => return Err(ParseError::at(input, a, "`A`, `B` or `C`")),
+    };
+
+    let player_two = match (player_one, b) {
+        (Rock, "X") => Scissors,
+        (Paper, "X") => Rock,
+        (Scissors, "X") => Paper,
+
+        (_, "Y") => player_one,
+
+        (Rock, "Z") => Paper,
+        (Paper, "Z") => Scissors,
+        (Scissors, "Z") => Rock,
+        _ => return Err(ParseError::at(input, b, "`X`, `Y` or `Z`")),
+    };
+
+    Ok(Play {
+        player_one,
+        player_two,
+    })
+}
 
-        use Hand::*;
-        let player_one = match a {
-            "A" => Rock,
-            "B" => Paper,
-            "C" => Scissors,
-            _ => return Err(eyre!("Invalid character")),
-        };
+fn total_score(plays: &[Play]) -> u32 {
+    plays.iter().map(Play::score).sum()
+}
 
-        let player_two = match (player_one, b) {
-            (Rock, "X") => Scissors,
-            (Paper, "X") => Rock,
-            (Scissors, "X") => Paper,
+/// The strategy guide, read both as the hand to play, and as the outcome to aim for.
+#[derive(Debug, Clone)]
+pub struct Guide {
+    by_hand: Vec<Play>,
+    by_outcome: Vec<Play>,
+}
 
-            (_, "Y") => player_one,
+pub type Parsed = Guide;
 
-            (Rock, "Z") => Paper,
-            (Paper, "Z") => Scissors,
-            (Scissors, "Z") => Rock,
-            _ => return Err(eyre!("Invalid character")),
-        };
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    Ok(Guide {
+        by_hand: parse_part1(input)?,
+        by_outcome: parse_part2(input)?,
+    })
+}
 
-        plays.push(Play {
-            player_one,
-            player_two,
-        });
-    }
+pub fn part1(guide: &Guide) -> u32 {
+    total_score(&guide.by_hand)
+}
 
-    Ok(plays)
+pub fn part2(guide: &Guide) -> u32 {
+    total_score(&guide.by_outcome)
 }
 
-fn part1(plays: &[Play]) -> u32 {
-    plays.iter().map(Play::score).sum()
+/// Solves both parts from a reader, a round at a time.
+///
+/// The totals are `u64`s, as a guide too long to fit in memory can easily score past `u32::MAX`.
+pub fn stream(reader: impl BufRead) -> Result<(u64, u64), DayError> {
+    let (mut by_hand, mut by_outcome) = (0, 0);
+    input::for_each_line(reader, |line| {
+        by_hand += u64::from(parse_play_part1(line, line)?.score());
+        by_outcome += u64::from(parse_play_part2(line, line)?.score());
+        Ok(())
+    })?;
+
+    Ok((by_hand, by_outcome))
 }
 
 const ROCK_X: u32 = u32::from_le_bytes(*b"A X\n");
@@ -162,54 +238,108 @@ const SCISSORS_X: u32 = u32::from_le_bytes(*b"C X\n");
 const SCISSORS_Y: u32 = u32::from_le_bytes(*b"C Y\n");
 const SCISSORS_Z: u32 = u32::from_le_bytes(*b"C Z\n");
 
-fn part1_no_alloc(input: &str) -> u16 {
-    assert!(input.len() % 4 == 0);
-
+/// Reads each round as a single `u32`, which only lines up when the input has been normalised to
+/// `LF` line endings and a final newline.
+fn part1_no_alloc(input: &str) -> Result<u32, ParseError> {
     let mut total_score = 0;
-    for chunk in ArrChunks::new(input.as_bytes()) {
+    for (idx, chunk) in ArrChunks::new(input.as_bytes()).enumerate() {
         let as_u32 = u32::from_ne_bytes(*chunk);
 
         let match_score = match as_u32 {
             ROCK_X | PAPER_Y | SCISSORS_Z => 3,
             ROCK_Y | PAPER_Z | SCISSORS_X => 6,
             ROCK_Z | PAPER_X | SCISSORS_Y => 0,
-            _ => unreachable!(),
+            _ => return Err(bad_round(input, idx * 4)),
         };
 
-        let hand_score = (chunk[2] - b'X' + 1) as u16;
+        let hand_score = (chunk[2] - b'X' + 1) as u32;
 
         total_score += hand_score + match_score;
     }
 
-    total_score
+    check_no_partial_round(input)?;
+    Ok(total_score)
 }
 
-fn part2_no_alloc(input: &str) -> u16 {
-    assert!(input.len() % 4 == 0);
-
+/// The same as [`part1_no_alloc`], scoring rounds by their outcome instead.
+fn part2_no_alloc(input: &str) -> Result<u32, ParseError> {
     let mut total_score = 0;
-    for chunk in ArrChunks::new(input.as_bytes()) {
+    for (idx, chunk) in ArrChunks::new(input.as_bytes()).enumerate() {
         let as_u32 = u32::from_ne_bytes(*chunk);
 
-        let match_score = ((chunk[2] - b'X') * 3) as u16;
-
         let hand_score = match as_u32 {
             ROCK_X | PAPER_Z | SCISSORS_Y => 3,
             ROCK_Y | PAPER_X | SCISSORS_Z => 1,
             ROCK_Z | PAPER_Y | SCISSORS_X => 2,
-            _ => unreachable!(),
+            _ => return Err(bad_round(input, idx * 4)),
         };
 
+        let match_score = ((chunk[2] - b'X') * 3) as u32;
+
         total_score += hand_score + match_score;
     }
 
-    total_score
+    check_no_partial_round(input)?;
+    Ok(total_score)
+}
+
+/// Every round before `offset` was read whole, so it's the start of the bad round's line.
+fn bad_round(input: &str, offset: usize) -> ParseError {
+    let line_len = input[offset..].find('\n').unwrap_or(input.len() - offset);
+    ParseError::at_offset(input, offset, line_len, "a round like `A Y`")
+}
+
+/// Catches a short last line, which the rounds before it being whole leaves over.
+fn check_no_partial_round(input: &str) -> Result<(), ParseError> {
+    match input.len() % 4 {
+        0 => Ok(()),
+        rem => Err(bad_round(input, input.len() - rem)),
+    }
 }
 
 #[cfg(test)]
-mod day01_tests {
+mod tests {
     use super::*;
+    use crate::budget::{self, Budget};
     use aoc_lib::Example;
+    use proptest::prelude::*;
+
+    #[test]
+    fn fast_invalid_round_test() {
+        for (input, line, column) in [
+            ("A Y\nA X \nB Z\n", 2, 1),
+            ("A Y\nA  X\n", 2, 1),
+            ("A Y\nB W\n", 2, 1),
+            ("A Y\nB\n", 2, 1),
+        ] {
+            for solve in [part1_no_alloc, part2_no_alloc] {
+                let error = solve(input).unwrap_err();
+                assert_eq!((line, column), (error.line, error.column), "{input:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn fast_past_u16_test() {
+        // Every round scores 9 in both parts, so this totals more than a `u16` can hold.
+        let input = "B Z\n".repeat(10_000);
+        assert_eq!(90_000, part1_no_alloc(&input).unwrap());
+        assert_eq!(90_000, part2_no_alloc(&input).unwrap());
+    }
+
+    #[test]
+    fn fast_budget_test() {
+        let input = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+        let input = solution::normalise::<Day02>(&input).unwrap();
+
+        let answer = budget::assert_within(Budget::NONE, || part1_no_alloc(&input).unwrap());
+        assert_eq!(15, answer);
+        let answer = budget::assert_within(Budget::NONE, || part2_no_alloc(&input).unwrap());
+        assert_eq!(12, answer);
+    }
 
     #[test]
     fn part1_test() {
@@ -243,29 +373,48 @@ mod day01_tests {
         }
     }
 
-    #[test]
-    fn part1_fast() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
+    fn input() -> impl Strategy<Value = String> {
+        let round = (
+            prop::sample::select(&["A", "B", "C"][..]),
+            prop::sample::select(&["X", "Y", "Z"][..]),
+        );
+        // Rounds score 5 points on average, so long guides total more than a `u16` can hold.
+        prop::collection::vec(round, 0..20_000)
+            .prop_map(|rounds| rounds.iter().map(|(a, b)| format!("{a} {b}\n")).collect())
+    }
 
-        let hand = part1_no_alloc(&data);
-        let expected_scores = 15;
+    proptest! {
+        // The guides are long, so fewer cases keeps the tests quick.
+        #![proptest_config(ProptestConfig::with_cases(32))]
 
-        assert_eq!(expected_scores, hand);
-    }
+        #[test]
+        fn part1_no_alloc_matches_parsed(input in input()) {
+            let guide = parse(&input).unwrap();
+            prop_assert_eq!(part1(&guide), part1_no_alloc(&input).unwrap());
+        }
 
-    #[test]
-    fn part2_fast() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
+        #[test]
+        fn part2_no_alloc_matches_parsed(input in input()) {
+            let guide = parse(&input).unwrap();
+            prop_assert_eq!(part2(&guide), part2_no_alloc(&input).unwrap());
+        }
 
-        let hand = part2_no_alloc(&data);
-        let expected_scores = 12;
+        #[test]
+        fn stream_matches_parsed(input in input()) {
+            prop_assume!(!input.is_empty());
+            let guide = parse(&input).unwrap();
+            let expected = (u64::from(part1(&guide)), u64::from(part2(&guide)));
+            prop_assert_eq!(expected, stream(input.as_bytes()).unwrap());
+        }
+    }
 
-        assert_eq!(expected_scores, hand);
+    #[test]
+    #[ignore = "reads 2GB of rounds; run with --release --ignored"]
+    fn stream_past_u32_test() {
+        // Each round scores 9 in both parts, so this passes u32::MAX by about 20 million.
+        let rounds = 480_000_000;
+        let expected = 9 * rounds as u64;
+        let reader = input::repeat_line("B Z", rounds);
+        assert_eq!((expected, expected), stream(reader).unwrap());
     }
 }
@@ -1,75 +1,126 @@
-use aoc_lib::{misc::ArrChunks, Bench, BenchResult, Day, NoError, ParseResult};
-use color_eyre::Report;
+use std::io::BufRead;
+
+use aoc_lib::{misc::ArrChunks, Day};
+
+use crate::{
+    input::{self, ParseError},
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
+};
 
 // 14:57
 // 15:28
 
-pub const DAY: Day = Day {
-    day: 3,
-    name: "Rucksack Reorganization",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[("Parse", run_parse)],
-};
+pub const DAY: Day = solution::day::<Day03>();
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data: Vec<_> = input.trim().lines().map(parse).collect();
-    b.bench(|| Ok::<_, NoError>(part1(&data)))
-}
+pub struct Day03;
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let data: Vec<_> = input.trim().lines().map(parse).collect();
-    b.bench(|| Ok::<_, NoError>(part2(&data)))
-}
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const NAME: &'static str = "Rucksack Reorganization";
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data: Vec<_> = input.lines().map(parse).collect();
-        Ok::<_, Report>(ParseResult(data))
-    })
+    type Parsed<'a> = Parsed;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<u32, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<u32, DayError> {
+        Ok(part2(data))
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Rucksack {
+pub struct Rucksack {
     slot1: u64,
     slot2: u64,
 }
 
-fn to_u64(slot: &str) -> u64 {
-    slot.bytes()
-        .map(|b| match b {
+fn to_u64(slot: &[u8]) -> Option<u64> {
+    slot.iter().try_fold(0, |acc, &b| {
+        let bit = match b {
             b'a'..=b'z' => 1 << ((b - b'a') as u64),
             b'A'..=b'Z' => (1 << ((b - b'A') as u64)) << 26,
-            _ => panic!("Invalid Character"),
-        })
-        .fold(0, |acc, bit| acc | bit)
+            _ => return None,
+        };
+        Some(acc | bit)
+    })
 }
 
-fn parse(line: &str) -> Rucksack {
-    let (slot1, slot2) = line.trim().split_at(line.len() / 2);
-
-    Rucksack {
-        slot1: to_u64(slot1),
-        slot2: to_u64(slot2),
+fn parse_rucksack(input: &str, line: &str) -> Result<Rucksack, ParseError> {
+    let line = line.trim();
+    let (slot1, slot2) = line.as_bytes().split_at(line.len() / 2);
+
+    match (to_u64(slot1), to_u64(slot2)) {
+        (Some(slot1), Some(slot2)) => Ok(Rucksack { slot1, slot2 }),
+        _ => {
+            let bad = line
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii_alphabetic())
+                .map_or(line, |(idx, c)| &line[idx..idx + c.len_utf8()]);
+            Err(ParseError::at(input, bad, "an item from `a` to `Z`"))
+        }
     }
 }
 
-fn part1(data: &[Rucksack]) -> u32 {
-    data.iter()
-        .map(|sack| {
-            let shared = sack.slot1 & sack.slot2;
-            shared.trailing_zeros() + 1
-        })
-        .sum()
+pub type Parsed = Vec<Rucksack>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    let rucksacks = input
+        .trim()
+        .lines()
+        .map(|line| parse_rucksack(input, line))
+        .collect::<Result<_, _>>()?;
+
+    Ok(rucksacks)
+}
+
+pub fn part1(data: &[Rucksack]) -> u32 {
+    data.iter().map(misplaced_priority).sum()
+}
+
+pub fn part2(data: &[Rucksack]) -> u32 {
+    ArrChunks::new(data).map(badge_priority).sum()
 }
 
-fn part2(data: &[Rucksack]) -> u32 {
-    ArrChunks::new(data)
-        .map(|[a, b, c]| {
-            let shared = (a.slot1 | a.slot2) & (b.slot1 | b.slot2) & (c.slot1 | c.slot2);
-            shared.trailing_zeros() + 1
-        })
-        .sum()
+fn misplaced_priority(sack: &Rucksack) -> u32 {
+    let shared = sack.slot1 & sack.slot2;
+    shared.trailing_zeros() + 1
+}
+
+fn badge_priority([a, b, c]: &[Rucksack; 3]) -> u32 {
+    let shared = (a.slot1 | a.slot2) & (b.slot1 | b.slot2) & (c.slot1 | c.slot2);
+    shared.trailing_zeros() + 1
+}
+
+/// Solves both parts from a reader, holding only the current group of three rucksacks.
+///
+/// The totals are `u64`s, as an input too large to fit in memory can easily pass `u32::MAX`.
+pub fn stream(reader: impl BufRead) -> Result<(u64, u64), DayError> {
+    let (mut misplaced, mut badges) = (0, 0);
+    let mut group = [Rucksack { slot1: 0, slot2: 0 }; 3];
+    let mut group_len = 0;
+    input::for_each_line(reader, |line| {
+        let sack = parse_rucksack(line, line)?;
+        misplaced += u64::from(misplaced_priority(&sack));
+
+        group[group_len] = sack;
+        group_len += 1;
+        if group_len == group.len() {
+            badges += u64::from(badge_priority(&group));
+            group_len = 0;
+        }
+        Ok(())
+    })?;
+
+    Ok((misplaced, badges))
 }
 
 #[cfg(test)]
@@ -78,32 +129,45 @@ mod tests {
     use aoc_lib::Example;
 
     #[test]
-    fn part1_test() {
+    fn stream_test() {
         let data = aoc_lib::input(DAY.day)
             .example(Example::Part1, 1)
             .open()
             .unwrap();
+        let rucksacks = parse(&data).unwrap();
 
-        let parsed: Vec<_> = data.lines().map(parse).collect();
+        let expected = (part1(&rucksacks), part2(&rucksacks));
+        let expected = (u64::from(expected.0), u64::from(expected.1));
+        assert_eq!(expected, stream(data.as_bytes()).unwrap());
 
-        let expected = 157;
-        let actual = part1(&parsed);
+        let data = data.replacen("jqHRNqRjqzjGDLGL", "jqHRNqRj4zjGDLGL", 1);
+        let error = stream(data.as_bytes()).unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((2, 9), (error.line, error.column));
+    }
 
-        assert_eq!(expected, actual);
+    #[test]
+    #[ignore = "reads 250MB of rucksacks; run with --release --ignored"]
+    fn stream_past_u32_test() {
+        // Every rucksack and group shares a `Z`, worth 52, so part 1 passes u32::MAX.
+        let sacks = 84_000_000;
+        let expected = (52 * sacks as u64, 52 * (sacks / 3) as u64);
+        assert_eq!(expected, stream(input::repeat_line("ZZ", sacks)).unwrap());
     }
 
     #[test]
-    fn part2_test() {
+    fn invalid_item_test() {
         let data = aoc_lib::input(DAY.day)
             .example(Example::Part1, 1)
             .open()
             .unwrap();
+        let data = data.replacen("jqHRNqRjqzjGDLGL", "jqHRNqRj4zjGDLGL", 1);
 
-        let parsed: Vec<_> = data.lines().map(parse).collect();
-
-        let expected = 70;
-        let actual = part2(&parsed);
+        let error = parse(&data).unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
 
-        assert_eq!(expected, actual);
+        assert_eq!(2, error.line);
+        assert_eq!(9, error.column);
+        assert_eq!("4", error.snippet);
     }
 }
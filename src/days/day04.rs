@@ -1,46 +1,67 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, io::BufRead};
 
-use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{eyre::eyre, Report, Result};
+use aoc_lib::{Bench, BenchResult, Day, NoError, UserError};
+use color_eyre::Result;
+
+use crate::{
+    input::{self, parse_at, ParseError},
+    solution::{self, run_parse, Part, Runner, Solution, Variant},
+    DayError,
+};
 
 // 11:35
 // 12:00
 
-pub const DAY: Day = Day {
-    day: 4,
-    name: "Camp Cleanup",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[
-        ("Parse", run_parse),
+pub const DAY: Day = solution::day::<Day04>();
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const NAME: &'static str = "Camp Cleanup";
+    const OTHER: &'static [(&'static str, Runner)] = &[
+        ("Parse", run_parse::<Self>),
         ("No Alloc Part 1", run_no_alloc_part1),
         ("No Alloc Part 2", run_no_alloc_part2),
-    ],
-};
-
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1(&data)))
-}
+    ];
+    const VARIANTS: &'static [Variant] = &[
+        Variant {
+            name: "No Alloc Part 1",
+            part: Part::One,
+            solve: |input| Ok(no_alloc_solve(input, no_alloc_part1_condition).to_string()),
+        },
+        Variant {
+            name: "No Alloc Part 2",
+            part: Part::Two,
+            solve: |input| Ok(no_alloc_solve(input, no_alloc_part2_condition).to_string()),
+        },
+    ];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part2(&data)))
-}
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part1(data))
+    }
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
+    fn part2(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part2(data))
+    }
 }
 
 fn run_no_alloc_part1(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| Ok::<_, NoError>(no_alloc_solve(input, no_alloc_part1_condition)))
+    let input = solution::normalise::<Day04>(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(no_alloc_solve(&input, no_alloc_part1_condition)))
 }
 
 fn run_no_alloc_part2(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| Ok::<_, NoError>(no_alloc_solve(input, no_alloc_part2_condition)))
+    let input = solution::normalise::<Day04>(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(no_alloc_solve(&input, no_alloc_part2_condition)))
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -50,57 +71,77 @@ struct Range {
 }
 
 #[derive(Debug, Clone)]
-struct Pair {
+pub struct Pair {
     elf1: Range,
     elf2: Range,
 }
 
-fn parse(input: &str) -> Result<Vec<Pair>, Report> {
-    let parse_pair = |string: &str| -> Result<Range, Report> {
+pub type Parsed = Vec<Pair>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    input
+        .lines()
+        .map(|line| Ok(parse_pair(input, line)?))
+        .collect()
+}
+
+fn parse_pair(input: &str, line: &str) -> Result<Pair, ParseError> {
+    let parse_range = |string: &str| -> Result<Range, ParseError> {
         let Some((start, end)) = string.split_once('-') else {
-            return Err(eyre!("Invalid range definition"));
+            return Err(ParseError::at(input, string, "a range like `2-4`"));
         };
 
-        let start = start.parse()?;
-        let end = end.parse()?;
+        let start = parse_at(input, start, "a section number")?;
+        let end = parse_at(input, end, "a section number")?;
         Ok(Range { start, end })
     };
 
-    input
-        .lines()
-        .map(|line| {
-            let Some((first, second)) = line.split_once(',') else {
-                return Err( eyre!("Invalid pair definition"));
-            };
-
-            Ok(Pair {
-                elf1: parse_pair(first)?,
-                elf2: parse_pair(second)?,
-            })
-        })
-        .collect()
+    let Some((first, second)) = line.split_once(',') else {
+        return Err(ParseError::at(input, line, "a pair like `2-4,6-8`"));
+    };
+
+    Ok(Pair {
+        elf1: parse_range(first)?,
+        elf2: parse_range(second)?,
+    })
 }
 
-fn part1(pairs: &[Pair]) -> usize {
-    pairs
-        .iter()
-        .filter(|p| match p.elf1.start.cmp(&p.elf2.start) {
-            Ordering::Less => p.elf2.end <= p.elf1.end,
-            Ordering::Greater => p.elf1.end <= p.elf2.end,
-            Ordering::Equal => true,
-        })
-        .count()
+pub fn part1(pairs: &[Pair]) -> usize {
+    pairs.iter().filter(|p| contains(p)).count()
 }
 
-fn part2(pairs: &[Pair]) -> usize {
-    pairs
-        .iter()
-        .filter(|p| match p.elf1.start.cmp(&p.elf2.start) {
-            Ordering::Less => p.elf2.start <= p.elf1.end,
-            Ordering::Equal => true,
-            Ordering::Greater => p.elf1.start <= p.elf2.end,
-        })
-        .count()
+pub fn part2(pairs: &[Pair]) -> usize {
+    pairs.iter().filter(|p| overlaps(p)).count()
+}
+
+/// Whether one elf's range contains the other's.
+fn contains(p: &Pair) -> bool {
+    match p.elf1.start.cmp(&p.elf2.start) {
+        Ordering::Less => p.elf2.end <= p.elf1.end,
+        Ordering::Greater => p.elf1.end <= p.elf2.end,
+        Ordering::Equal => true,
+    }
+}
+
+fn overlaps(p: &Pair) -> bool {
+    match p.elf1.start.cmp(&p.elf2.start) {
+        Ordering::Less => p.elf2.start <= p.elf1.end,
+        Ordering::Equal => true,
+        Ordering::Greater => p.elf1.start <= p.elf2.end,
+    }
+}
+
+/// Solves both parts from a reader, a pair at a time.
+pub fn stream(reader: impl BufRead) -> Result<(usize, usize), DayError> {
+    let (mut contained, mut overlapping) = (0, 0);
+    input::for_each_line(reader, |line| {
+        let pair = parse_pair(line, line)?;
+        contained += contains(&pair) as usize;
+        overlapping += overlaps(&pair) as usize;
+        Ok(())
+    })?;
+
+    Ok((contained, overlapping))
 }
 
 fn no_alloc_part1_condition(numbers: [u8; 4]) -> bool {
@@ -119,7 +160,9 @@ fn no_alloc_part2_condition(numbers: [u8; 4]) -> bool {
     }
 }
 
-fn no_alloc_solve(input: &str, nums: fn([u8; 4]) -> bool) -> u16 {
+/// Counts the pairs matching `nums` in a single pass over the bytes. It expects normalised input, as
+/// a carriage return would be read as a digit.
+fn no_alloc_solve(input: &str, nums: fn([u8; 4]) -> bool) -> usize {
     let mut count = 0;
 
     let mut numbers = [0; 4];
@@ -127,7 +170,7 @@ fn no_alloc_solve(input: &str, nums: fn([u8; 4]) -> bool) -> u16 {
     for byte in input.bytes() {
         if byte == b'\n' {
             // We've hit the end of the line, do the check.
-            count += nums(numbers) as u16;
+            count += nums(numbers) as usize;
 
             numbers_idx = 0;
             numbers = [0; 4];
@@ -146,59 +189,70 @@ fn no_alloc_solve(input: &str, nums: fn([u8; 4]) -> bool) -> u16 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::budget::{self, Budget};
     use aoc_lib::Example;
+    use proptest::prelude::*;
 
     #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
+    fn no_alloc_budget_test() {
+        let input = aoc_lib::input(DAY.day)
             .example(Example::Part1, 1)
             .open()
             .unwrap();
 
-        let pairs = parse(&data).unwrap();
-        let expected = 2;
-        let actual = part1(&pairs);
-
-        assert_eq!(expected, actual);
+        let answer = budget::assert_within(Budget::NONE, || {
+            no_alloc_solve(&input, no_alloc_part1_condition)
+        });
+        assert_eq!(2, answer);
+        let answer = budget::assert_within(Budget::NONE, || {
+            no_alloc_solve(&input, no_alloc_part2_condition)
+        });
+        assert_eq!(4, answer);
     }
 
-    #[test]
-    fn part2_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let pairs = parse(&data).unwrap();
-        let expected = 4;
-        let actual = part2(&pairs);
+    fn input() -> impl Strategy<Value = String> {
+        let range = (1..100u8, 1..100u8).prop_map(|(a, b)| (a.min(b), a.max(b)));
+        prop::collection::vec((range.clone(), range), 0..200).prop_map(|pairs| {
+            pairs
+                .iter()
+                .map(|((a, b), (c, d))| format!("{a}-{b},{c}-{d}\n"))
+                .collect()
+        })
+    }
 
-        assert_eq!(expected, actual);
+    /// Inputs with more matching pairs than a `u16` can count, made by following a random input
+    /// with enough pairs which match both parts.
+    fn long_input() -> impl Strategy<Value = String> {
+        (input(), 0..1000usize)
+            .prop_map(|(input, extra)| input + &"2-4,3-3\n".repeat(u16::MAX as usize + extra))
     }
 
-    #[test]
-    fn no_alloc_part1_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
+    proptest! {
+        // The inputs are long, so fewer cases keeps the tests quick.
+        #![proptest_config(ProptestConfig::with_cases(32))]
 
-        let expected = 2;
-        let actual = no_alloc_solve(&data, no_alloc_part1_condition);
+        #[test]
+        fn part1_no_alloc_matches_parsed(input in long_input()) {
+            let pairs = parse(&input).unwrap();
+            let expected = part1(&pairs);
+            prop_assert_eq!(expected, no_alloc_solve(&input, no_alloc_part1_condition));
+        }
 
-        assert_eq!(expected, actual);
+        #[test]
+        fn part2_no_alloc_matches_parsed(input in long_input()) {
+            let pairs = parse(&input).unwrap();
+            let expected = part2(&pairs);
+            prop_assert_eq!(expected, no_alloc_solve(&input, no_alloc_part2_condition));
+        }
     }
 
-    #[test]
-    fn no_alloc_part2_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let expected = 4;
-        let actual = no_alloc_solve(&data, no_alloc_part2_condition);
-
-        assert_eq!(expected, actual);
+    proptest! {
+        #[test]
+        fn stream_matches_parsed(input in input()) {
+            prop_assume!(!input.is_empty());
+            let pairs = parse(&input).unwrap();
+            let expected = (part1(&pairs), part2(&pairs));
+            prop_assert_eq!(expected, stream(input.as_bytes()).unwrap());
+        }
     }
 }
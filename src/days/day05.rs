@@ -1,32 +1,39 @@
-use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{eyre::eyre, Report, Result};
+use aoc_lib::Day;
+use color_eyre::{eyre::eyre, Result};
+
+use crate::{
+    input::{parse_at, ParseError},
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
+};
 
 // 12:31
 // 13:35
 
-pub const DAY: Day = Day {
-    day: 5,
-    name: "Supply Stacks",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[("Parse", run_parse)],
-};
+pub const DAY: Day = solution::day::<Day05>();
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(solve::<false>(&data)))
-}
+pub struct Day05;
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(solve::<true>(&data)))
-}
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const NAME: &'static str = "Supply Stacks";
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = String;
+    type Answer2 = String;
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<String, DayError> {
+        part1(data)
+    }
+
+    fn part2(data: &Parsed) -> Result<String, DayError> {
+        part2(data)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -37,23 +44,25 @@ struct Step {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Supply {
+pub struct Supply {
     stack: Vec<Vec<u8>>,
     procedure: Vec<Step>,
 }
 
-fn parse_stack(stack_str: &str) -> Result<Vec<Vec<u8>>> {
+fn parse_stack(input: &str, stack_str: &str) -> Result<Vec<Vec<u8>>> {
     let num_lines = stack_str.lines().count();
     let Some(last_line) = stack_str
         .bytes()
         .rposition(|b| b == b'\n')
-        .map(|idx| &stack_str[idx + 1..]) else {
-            return Err(eyre!("Invalid stack definition"));
-        };
+        .map(|idx| &stack_str[idx + 1..])
+    else {
+        let expected = "a drawing of the stacks above their numbers";
+        return Err(ParseError::at(input, stack_str, expected).into());
+    };
 
     // Oh this is so brittle...
     let Some(num_stacks) = last_line.trim_end().chars().last().and_then(|c| c.to_digit(10)).map(|d| d as usize) else {
-        return Err(eyre!("Invalid last column"));
+        return Err(ParseError::at(input, last_line, "the numbers of the stacks").into());
     };
 
     let mut stacks = vec![Vec::new(); num_stacks];
@@ -75,11 +84,11 @@ fn parse_stack(stack_str: &str) -> Result<Vec<Vec<u8>>> {
     Ok(stacks)
 }
 
-fn parse_procedure(procedure: &str) -> Result<Vec<Step>> {
+fn parse_procedure(input: &str, procedure: &str) -> Result<Vec<Step>> {
     fn split_line(line: &str) -> Option<(&str, &str, &str)> {
-        let (count, rest) = line[5..].split_once(' ')?;
-        let (from, rest) = rest[5..].split_once(' ')?;
-        let to = &rest[3..];
+        let line = line.strip_prefix("move ")?;
+        let (count, rest) = line.split_once(" from ")?;
+        let (from, to) = rest.split_once(" to ")?;
 
         Some((count, from, to))
     }
@@ -88,37 +97,60 @@ fn parse_procedure(procedure: &str) -> Result<Vec<Step>> {
 
     for line in procedure.lines() {
         let Some((count, from, to)) = split_line(line) else {
-            return Err(eyre!("Invalid step definition"));
+            return Err(ParseError::at(input, line, "a step like `move 1 from 2 to 3`").into());
         };
 
         steps.push(Step {
-            count: count.parse()?,
-            from: from.parse()?,
-            to: to.parse()?,
+            count: parse_at(input, count, "a number of crates")?,
+            from: parse_at(input, from, "a stack number")?,
+            to: parse_at(input, to, "a stack number")?,
         })
     }
 
     Ok(steps)
 }
 
-fn parse(input: &str) -> Result<Supply> {
+pub type Parsed = Supply;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
     let Some((stack, procedure)) = input.split_once("\n\n") else {
-        return Err(eyre!("Invalid input"));
+        let end = &input.trim_end()[input.trim_end().len()..];
+        return Err(ParseError::at(input, end, "a blank line before the procedure").into());
     };
 
-    let stack = parse_stack(stack)?;
-    let procedure = parse_procedure(procedure)?;
+    let stack = parse_stack(input, stack)?;
+    let procedure = parse_procedure(input, procedure)?;
     Ok(Supply { stack, procedure })
 }
 
-fn solve<const KEEP_ORDER: bool>(supply: &Supply) -> String {
+pub fn part1(supply: &Supply) -> Result<String> {
+    solve::<false>(supply)
+}
+
+pub fn part2(supply: &Supply) -> Result<String> {
+    solve::<true>(supply)
+}
+
+fn solve<const KEEP_ORDER: bool>(supply: &Supply) -> Result<String> {
     let mut stacks = supply.stack.clone();
     let total_len = stacks.iter().map(|s| s.len()).sum();
     stacks.iter_mut().for_each(|s| s.reserve(total_len));
 
-    for step in &supply.procedure {
+    for (i, step) in supply.procedure.iter().enumerate() {
+        let num_stacks = stacks.len();
         if step.from == step.to {
-            panic!("wut");
+            return Err(eyre!(
+                "Step {} moves stack {} onto itself",
+                i + 1,
+                step.from
+            ));
+        }
+        if !(1..=num_stacks).contains(&step.from) || !(1..=num_stacks).contains(&step.to) {
+            return Err(eyre!(
+                "Step {} refers to a stack outside 1 to {}",
+                i + 1,
+                num_stacks
+            ));
         }
 
         let from_idx = step.from - 1;
@@ -133,7 +165,15 @@ fn solve<const KEEP_ORDER: bool>(supply: &Supply) -> String {
             (&mut part1[from_idx], &mut part2[0])
         };
 
-        let start_idx = from_stack.len() - step.count;
+        let start_idx = from_stack.len().checked_sub(step.count).ok_or_else(|| {
+            eyre!(
+                "Step {} moves {} crates from stack {}, which only has {}",
+                i + 1,
+                step.count,
+                step.from,
+                from_stack.len()
+            )
+        })?;
         if !KEEP_ORDER {
             from_stack[start_idx..].reverse();
         }
@@ -141,11 +181,13 @@ fn solve<const KEEP_ORDER: bool>(supply: &Supply) -> String {
         from_stack.truncate(start_idx);
     }
 
-    stacks
+    let tops = stacks
         .into_iter()
         .filter_map(|s| s.last().copied())
         .map(|b| b as char)
-        .collect()
+        .collect();
+
+    Ok(tops)
 }
 
 #[cfg(test)]
@@ -191,28 +233,32 @@ mod tests {
     }
 
     #[test]
-    fn part1_test() {
+    fn invalid_step_test() {
         let data = aoc_lib::input(DAY.day)
             .example(Example::Parse, 1)
             .open()
             .unwrap();
+        let data = data.replace("move 3 from 1 to 3", "move 3 from one to 3");
 
-        let supply = parse(&data).unwrap();
-        let expected = "CMZ";
-        let actual = solve::<false>(&supply);
-        assert_eq!(expected, actual);
+        let error = parse(&data).unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+
+        assert_eq!(7, error.line);
+        assert_eq!(13, error.column);
+        assert_eq!("one", error.snippet);
     }
 
     #[test]
-    fn part2_test() {
+    fn too_many_crates_test() {
         let data = aoc_lib::input(DAY.day)
             .example(Example::Parse, 1)
             .open()
             .unwrap();
+        let data = data.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
 
         let supply = parse(&data).unwrap();
-        let expected = "MCD";
-        let actual = solve::<true>(&supply);
-        assert_eq!(expected, actual);
+
+        assert!(part1(&supply).is_err());
+        assert!(part2(&supply).is_err());
     }
 }
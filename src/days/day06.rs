@@ -1,22 +1,58 @@
-use aoc_lib::{misc::ArrWindows, Bench, BenchResult, Day, NoError};
+use aoc_lib::{misc::ArrWindows, Day};
+use color_eyre::eyre::eyre;
+
+use crate::{
+    input::Shape,
+    solution::{self, Solution},
+    DayError,
+};
 
 // 11:40
 // 11:57
 
-pub const DAY: Day = Day {
-    day: 6,
-    name: "Tuning Trouble",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[],
-};
+pub const DAY: Day = solution::day::<Day06>();
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const NAME: &'static str = "Tuning Trouble";
+    const INPUT: Shape = Shape::Line;
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part2(data))
+    }
+}
+
+pub type Parsed = String;
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| Ok::<_, NoError>(solve::<4>(input)))
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    let signal = input.trim();
+    if !signal.bytes().all(|b| b.is_ascii_lowercase()) {
+        return Err(eyre!("Invalid character in signal"));
+    }
+
+    Ok(signal.to_owned())
+}
+
+pub fn part1(signal: &str) -> usize {
+    solve::<4>(signal)
 }
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| Ok::<_, NoError>(solve::<14>(input)))
+pub fn part2(signal: &str) -> usize {
+    solve::<14>(signal)
 }
 
 fn solve<const N: usize>(data: &str) -> usize {
@@ -35,47 +71,3 @@ fn solve<const N: usize>(data: &str) -> usize {
         .0
         + N
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc_lib::Example;
-
-    #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let tests = data.lines().map(|l| {
-            let (test, expected) = l.split_once('-').unwrap();
-            let (expected, _) = expected.split_once(',').unwrap();
-            (test, expected.parse::<usize>().unwrap())
-        });
-
-        for (test, expected) in tests {
-            let actual = solve::<4>(test);
-            assert_eq!(expected, actual);
-        }
-    }
-
-    #[test]
-    fn part2_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let tests = data.lines().map(|l| {
-            let (test, expected) = l.split_once('-').unwrap();
-            let (_, expected) = expected.split_once(',').unwrap();
-            (test, expected.parse::<usize>().unwrap())
-        });
-
-        for (test, expected) in tests {
-            let actual = solve::<14>(test);
-            assert_eq!(expected, actual);
-        }
-    }
-}
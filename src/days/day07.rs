@@ -1,32 +1,39 @@
-use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{eyre::eyre, Report, Result};
+use aoc_lib::Day;
+use color_eyre::{eyre::eyre, Result};
+
+use crate::{
+    input::{parse_at, ParseError},
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
+};
 
 // 12:01
 // 13:26
 
-pub const DAY: Day = Day {
-    day: 7,
-    name: "No Space Left On Device",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[("Parse", run_parse)],
-};
+pub const DAY: Day = solution::day::<Day07>();
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1(&data)))
-}
+pub struct Day07;
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part2(&data)))
-}
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const NAME: &'static str = "No Space Left On Device";
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
+
+    type Parsed<'a> = Parsed<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
+    fn parse(input: &str) -> Result<Parsed<'_>, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed<'_>) -> Result<usize, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed<'_>) -> Result<usize, DayError> {
+        Ok(part2(data))
+    }
 }
 
 #[derive(Debug, Clone, Copy, Hash)]
@@ -69,7 +76,7 @@ impl<'a> FileSystemEntry<'a> {
 }
 
 #[derive(Debug)]
-struct FileSystem<'a> {
+pub struct FileSystem<'a> {
     root: EntryId,
     entries: Vec<FileSystemEntry<'a>>,
 }
@@ -94,10 +101,17 @@ impl<'a> FileSystem<'a> {
         name: &'a str,
         parent: EntryId,
         kind: FileSystemEntryKind,
-    ) -> &mut FileSystemEntry<'a> {
+    ) -> Result<&mut FileSystemEntry<'a>> {
         let next_id = EntryId(self.entries.len());
+        let parent_name = self.entries[parent.0].name;
         match &mut self.entries[parent.0].kind {
-            FileSystemEntryKind::File { .. } => panic!("tried to add child to file"),
+            FileSystemEntryKind::File { .. } => {
+                return Err(eyre!(
+                    "Tried to add `{}` to the file `{}`",
+                    name,
+                    parent_name
+                ))
+            }
             FileSystemEntryKind::Directory { children } => children.push(next_id),
         }
 
@@ -109,7 +123,7 @@ impl<'a> FileSystem<'a> {
         };
 
         self.entries.push(new_dir);
-        &mut self.entries[next_id.0]
+        Ok(&mut self.entries[next_id.0])
     }
 
     fn get_children(&self, entry: EntryId) -> &[EntryId] {
@@ -152,7 +166,9 @@ impl<'a> FileSystem<'a> {
     }
 }
 
-fn parse(input: &str) -> Result<FileSystem> {
+pub type Parsed<'a> = FileSystem<'a>;
+
+pub fn parse(input: &str) -> Result<Parsed<'_>, DayError> {
     let mut lines = input.lines().peekable();
     let mut fs = FileSystem::new();
     let mut cur_dir_id = fs.root();
@@ -162,7 +178,7 @@ fn parse(input: &str) -> Result<FileSystem> {
             loop {
                 let Some(next) = lines.peek().copied() else { break };
                 let Some((kind, name)) = next.split_once(' ') else {
-                    return Err(eyre!("Unexpected input: {:?}", next));
+                    return Err(ParseError::at(input, next, "a directory or file").into());
                 };
 
                 let entry_kind = match kind {
@@ -180,14 +196,14 @@ fn parse(input: &str) -> Result<FileSystem> {
                             continue;
                         }
 
-                        FileSystemEntryKind::file(kind.parse().unwrap())
+                        FileSystemEntryKind::file(parse_at(input, kind, "a file size")?)
                     }
                     _ => {
-                        return Err(eyre!("Unexpected input: {:?}", line));
+                        return Err(ParseError::at(input, kind, "`dir` or a file size").into());
                     }
                 };
 
-                fs.new_entry(name, cur_dir_id, entry_kind);
+                fs.new_entry(name, cur_dir_id, entry_kind)?;
                 lines.next();
             }
         } else if let Some(dir_name) = line.strip_prefix("$ cd ") {
@@ -198,7 +214,7 @@ fn parse(input: &str) -> Result<FileSystem> {
                 cur_dir_id = if let Some(cd) = cur_dir.parent {
                     cd
                 } else {
-                    return Err(eyre!("Invalid directory travarsal"));
+                    return Err(ParseError::at(input, dir_name, "a directory inside `/`").into());
                 };
             } else {
                 let is_known_dir = fs
@@ -211,19 +227,19 @@ fn parse(input: &str) -> Result<FileSystem> {
                 cur_dir_id = if let Some(new_id) = is_known_dir {
                     new_id.id
                 } else {
-                    fs.new_entry(dir_name, cur_dir_id, FileSystemEntryKind::dir())
+                    fs.new_entry(dir_name, cur_dir_id, FileSystemEntryKind::dir())?
                         .id
                 };
             }
         } else {
-            return Err(eyre!("Unexpected input: {:?}", line));
+            return Err(ParseError::at(input, line, "a `$ cd` or `$ ls` command").into());
         }
     }
 
     Ok(fs)
 }
 
-fn part1(fs: &FileSystem) -> usize {
+pub fn part1(fs: &FileSystem) -> usize {
     fs.entries()
         .filter(|e| e.is_directory())
         .map(|d| fs.size_of_entry(d.id))
@@ -231,7 +247,7 @@ fn part1(fs: &FileSystem) -> usize {
         .sum()
 }
 
-fn part2(fs: &FileSystem) -> usize {
+pub fn part2(fs: &FileSystem) -> usize {
     const TOTAL_FS_SIZE: usize = 70_000_000;
     const NEEDED_SPACE: usize = 30_000_000;
 
@@ -247,37 +263,3 @@ fn part2(fs: &FileSystem) -> usize {
         .min()
         .unwrap()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc_lib::Example;
-
-    #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let tree = parse(&data).unwrap();
-        let expected = 95437;
-        let actual = part1(&tree);
-
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn part2_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let tree = parse(&data).unwrap();
-        let expected = 24_933_642;
-        let actual = part2(&tree);
-
-        assert_eq!(expected, actual);
-    }
-}
@@ -1,114 +1,116 @@
-use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{Report, Result};
+use aoc_lib::Day;
+use color_eyre::Result;
+
+use crate::{
+    grid::Grid,
+    input::Shape,
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
+};
 
 // 10:16
 // 11:26
 
-pub const DAY: Day = Day {
-    day: 8,
-    name: "Treetop Tree House",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[("Parse", run_parse)],
-};
+pub const DAY: Day = solution::day::<Day08>();
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1(&data)))
-}
+pub struct Day08;
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part2(&data)))
-}
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const NAME: &'static str = "Treetop Tree House";
+    const INPUT: Shape = Shape::Grid;
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
-}
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
 
-struct Grid {
-    size: usize,
-    trees: Vec<i8>,
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part2(data))
+    }
 }
 
-fn parse(input: &str) -> Result<Grid> {
-    let width = input.as_bytes().iter().position(|&d| d == b'\n').unwrap();
+pub type Parsed = Grid<i8>;
 
-    let trees = input
-        .as_bytes()
-        .iter()
-        .filter(|b| b.is_ascii_digit())
-        .map(|d| (d - b'0') as i8)
-        .collect();
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    let trees = Grid::parse(input, "a tree height from 0 to 9", |_, b| {
+        b.is_ascii_digit().then_some((b - b'0') as i8)
+    })?;
 
-    Ok(Grid { size: width, trees })
+    Ok(trees)
 }
 
-fn part1(trees: &Grid) -> usize {
+pub fn part1(trees: &Grid<i8>) -> usize {
     // We know the outside trees are always visible.
-    let mut visibility = vec![false; trees.trees.len()];
+    let mut visibility = Grid::new(trees.width(), trees.height(), false);
 
-    let mut check = |idx: usize, max: &mut i8| {
-        let tree_height = trees.trees[idx];
+    let mut check = |x: usize, y: usize, max: &mut i8| {
+        let tree_height = trees[(x, y)];
         if tree_height > *max {
-            visibility[idx] = true;
+            visibility[(x, y)] = true;
             *max = tree_height;
         }
     };
 
-    for y in 0..trees.size {
+    for y in 0..trees.height() {
         let mut max_height_left = -1;
         let mut max_height_right = -1;
-        let mut max_height_top = -1;
-        let mut max_height_bottom = -1;
-        for x in 0..trees.size {
+        for x in 0..trees.width() {
             // Left > Right
-            let idx = y * trees.size + x;
-            check(idx, &mut max_height_left);
+            check(x, y, &mut max_height_left);
 
             // Right > Left
-            let idx = y * trees.size + (trees.size - x - 1);
-            check(idx, &mut max_height_right);
+            check(trees.width() - x - 1, y, &mut max_height_right);
+        }
+    }
 
+    for x in 0..trees.width() {
+        let mut max_height_top = -1;
+        let mut max_height_bottom = -1;
+        for y in 0..trees.height() {
             // Top > Bottom
-            let idx = x * trees.size + y;
-            check(idx, &mut max_height_top);
+            check(x, y, &mut max_height_top);
 
             // Bottom > Top
-            let idx = (trees.size - x - 1) * trees.size + y;
-            check(idx, &mut max_height_bottom);
+            check(x, trees.height() - y - 1, &mut max_height_bottom);
         }
     }
 
-    visibility.into_iter().filter(|v| *v).count()
+    visibility.iter().filter(|(_, v)| **v).count()
 }
 
-fn part2(trees: &Grid) -> usize {
+pub fn part2(trees: &Grid<i8>) -> usize {
     let mut max_score = 0;
-    assert!(trees.size != 0);
+    let (width, height) = (trees.width(), trees.height());
 
-    for y in 1..trees.size - 1 {
-        for x in 1..trees.size - 1 {
-            let tree = *unsafe { trees.trees.get_unchecked(y * trees.size + x) };
+    // The unchecked lookups are all within `0..width` and `0..height`.
+    let get = |x, y| *unsafe { trees.get_unchecked(x, y) };
+
+    for y in 1..height.saturating_sub(1) {
+        for x in 1..width.saturating_sub(1) {
+            let tree = get(x, y);
 
             // Do the search leftwards.
             let mut left_score = x;
             for new_x in (0..x).rev() {
-                let idx = y * trees.size + new_x;
-                if *unsafe { trees.trees.get_unchecked(idx) } >= tree {
+                if get(new_x, y) >= tree {
                     left_score = x - new_x;
                     break;
                 }
             }
 
             // Rightwards search.
-            let mut right_score = trees.size - x - 1;
-            for new_x in x + 1..trees.size {
-                let idx = y * trees.size + new_x;
-                if *unsafe { trees.trees.get_unchecked(idx) } >= tree {
+            let mut right_score = width - x - 1;
+            for new_x in x + 1..width {
+                if get(new_x, y) >= tree {
                     right_score = new_x - x;
                     break;
                 }
@@ -117,18 +119,16 @@ fn part2(trees: &Grid) -> usize {
             // Upwards search.
             let mut up_score = y;
             for new_y in (0..y).rev() {
-                let idx = new_y * trees.size + x;
-                if *unsafe { trees.trees.get_unchecked(idx) } >= tree {
+                if get(x, new_y) >= tree {
                     up_score = y - new_y;
                     break;
                 }
             }
 
             // Downwards search.
-            let mut down_score = trees.size - y - 1;
-            for new_y in y + 1..trees.size {
-                let idx = new_y * trees.size + x;
-                if *unsafe { trees.trees.get_unchecked(idx) } >= tree {
+            let mut down_score = height - y - 1;
+            for new_y in y + 1..height {
+                if get(x, new_y) >= tree {
                     down_score = new_y - y;
                     break;
                 }
@@ -140,37 +140,3 @@ fn part2(trees: &Grid) -> usize {
 
     max_score
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc_lib::Example;
-
-    #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let trees = parse(&data).unwrap();
-        let expected = 21;
-        let actual = part1(&trees);
-
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn part2_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let trees = parse(&data).unwrap();
-        let expected = 8;
-        let actual = part2(&trees);
-
-        assert_eq!(expected, actual);
-    }
-}
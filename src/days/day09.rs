@@ -1,64 +1,48 @@
-use std::collections::HashSet;
-
-use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{eyre::eyre, Report, Result};
-use derive_more::{Add, Sub};
-
-pub const DAY: Day = Day {
-    day: 9,
-    name: "Rope Bridge",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[("Parse", run_parse)],
+use std::{collections::HashSet, io::BufRead};
+
+use aoc_lib::Day;
+use color_eyre::Result;
+
+use crate::{
+    geometry::{Direction, Point2},
+    input::{self, parse_at, ParseError},
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
 };
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1::<2>(&data)))
-}
+pub const DAY: Day = solution::day::<Day09>();
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1::<10>(&data)))
-}
+pub struct Day09;
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
-}
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const NAME: &'static str = "Rope Bridge";
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-impl Direction {
-    fn to_relative_position(self) -> Position {
-        match self {
-            Direction::Up => Position { x: 0, y: -1 },
-            Direction::Down => Position { x: 0, y: 1 },
-            Direction::Left => Position { x: -1, y: 0 },
-            Direction::Right => Position { x: 1, y: 0 },
-        }
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part2(data))
     }
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Move {
+pub struct Move {
     dir: Direction,
     distance: u8,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, Add, Sub)]
-struct Position {
-    x: i16,
-    y: i16,
-}
+type Position = Point2<i16>;
 
 #[derive(Debug)]
 struct Rope<const N: usize> {
@@ -73,7 +57,7 @@ impl<const N: usize> Rope<N> {
     }
 
     fn step(&mut self, dir: Direction) {
-        self.segments[0] = self.segments[0] + dir.to_relative_position();
+        self.segments[0] += dir.offset();
 
         for i in 0..N - 1 {
             let [ head, tail, .. ] = &mut self.segments[i..] else {
@@ -82,12 +66,8 @@ impl<const N: usize> Rope<N> {
 
             let distance = *head - *tail;
 
-            if distance.x.abs() > 1 || distance.y.abs() > 1 {
-                let new_move = Position {
-                    x: distance.x.clamp(-1, 1),
-                    y: distance.y.clamp(-1, 1),
-                };
-                *tail = *tail + new_move;
+            if distance.chebyshev() > 1 {
+                *tail += distance.signum();
             }
         }
     }
@@ -97,89 +77,98 @@ impl<const N: usize> Rope<N> {
     }
 }
 
-fn parse(input: &str) -> Result<Vec<Move>> {
-    input
-        .lines()
-        .map(|l| {
-            let Some((dir, distance)) = l.split_once(' ') else {
-            return Err(eyre!("Invalid move"));
-        };
-            let distance = distance.parse()?;
-            let dir = match dir {
-                "U" => Direction::Up,
-                "D" => Direction::Down,
-                "L" => Direction::Left,
-                "R" => Direction::Right,
-                _ => return Err(eyre!("Invalid move")),
-            };
-            Ok(Move { dir, distance })
-        })
-        .collect()
+pub type Parsed = Vec<Move>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    input.lines().map(|l| parse_move(input, l)).collect()
 }
 
-fn part1<const N: usize>(moves: &[Move]) -> usize {
-    let mut rope = Rope::<N>::new();
-    let mut visited = HashSet::new();
-    visited.insert(rope.tail());
-
-    let mut last_tail_pos = rope.tail();
-    for mov in moves {
-        (0..mov.distance).for_each(|_| {
-            rope.step(mov.dir);
-            if rope.tail() != last_tail_pos {
-                visited.insert(rope.tail());
-                last_tail_pos = rope.tail();
-            }
-        });
-    }
+fn parse_move(input: &str, line: &str) -> Result<Move> {
+    let Some((dir, distance)) = line.split_once(' ') else {
+        return Err(ParseError::at(input, line, "a move like `R 4`").into());
+    };
+    let distance = parse_at(input, distance, "a distance")?;
+    let dir = match dir {
+        "U" => Direction::Up,
+        "D" => Direction::Down,
+        "L" => Direction::Left,
+        "R" => Direction::Right,
+        _ => return Err(ParseError::at(input, dir, "`U`, `D`, `L` or `R`").into()),
+    };
+    Ok(Move { dir, distance })
+}
 
-    visited.len()
+pub fn part1(moves: &[Move]) -> usize {
+    tail_visits::<2>(moves)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc_lib::Example;
+pub fn part2(moves: &[Move]) -> usize {
+    tail_visits::<10>(moves)
+}
 
-    #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
+fn tail_visits<const N: usize>(moves: &[Move]) -> usize {
+    let mut tracker = TailTracker::<N>::new();
+    moves.iter().for_each(|&mov| tracker.apply(mov));
+    tracker.visited.len()
+}
 
-        let moves = parse(&data).unwrap();
-        let expected = 13;
-        let actual = part1::<2>(&moves);
+/// Moves a rope, and records every position its tail visits.
+struct TailTracker<const N: usize> {
+    rope: Rope<N>,
+    visited: HashSet<Position>,
+    last_tail_pos: Position,
+}
 
-        assert_eq!(expected, actual);
+impl<const N: usize> TailTracker<N> {
+    fn new() -> Self {
+        let mut rope = Rope::<N>::new();
+        let last_tail_pos = rope.tail();
+        Self {
+            rope,
+            visited: HashSet::from([last_tail_pos]),
+            last_tail_pos,
+        }
     }
 
-    #[test]
-    fn part2_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
+    fn apply(&mut self, mov: Move) {
+        for _ in 0..mov.distance {
+            self.rope.step(mov.dir);
+            if self.rope.tail() != self.last_tail_pos {
+                self.visited.insert(self.rope.tail());
+                self.last_tail_pos = self.rope.tail();
+            }
+        }
+    }
+}
 
-        let moves = parse(&data).unwrap();
-        let expected = 1;
-        let actual = part1::<10>(&moves);
+/// Solves both parts from a reader, a move at a time. Only the positions the tails have visited
+/// are kept, which grows with the area the rope covers rather than the number of moves.
+pub fn stream(reader: impl BufRead) -> Result<(usize, usize), DayError> {
+    let mut short = TailTracker::<2>::new();
+    let mut long = TailTracker::<10>::new();
+    input::for_each_line(reader, |line| {
+        let mov = parse_move(line, line)?;
+        short.apply(mov);
+        long.apply(mov);
+        Ok(())
+    })?;
+
+    Ok((short.visited.len(), long.visited.len()))
+}
 
-        assert_eq!(expected, actual);
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
 
     #[test]
-    fn part2_test_2() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part2, 1)
-            .open()
-            .unwrap();
+    fn stream_test() {
+        for example in [Example::Part1, Example::Part2] {
+            let data = aoc_lib::input(DAY.day).example(example, 1).open().unwrap();
+            let moves = parse(&data).unwrap();
 
-        let moves = parse(&data).unwrap();
-        let expected = 36;
-        let actual = part1::<10>(&moves);
-
-        assert_eq!(expected, actual);
+            let expected = (part1(&moves), part2(&moves));
+            assert_eq!(expected, stream(data.as_bytes()).unwrap());
+        }
     }
 }
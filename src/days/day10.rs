@@ -1,33 +1,43 @@
-use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{eyre::eyre, Report, Result};
-
-pub const DAY: Day = Day {
-    day: 10,
-    name: "Cathode-Ray Tube",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[("Parse", run_parse)],
+use std::io::BufRead;
+
+use aoc_lib::Day;
+use color_eyre::Result;
+
+use crate::{
+    input::{self, parse_at, ParseError},
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
 };
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1(&data)))
-}
+pub const DAY: Day = solution::day::<Day10>();
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench_alt(|| Ok::<_, NoError>(part2(&data)))
-}
+pub struct Day10;
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const NAME: &'static str = "Cathode-Ray Tube";
+    const PART_2_ALT: bool = true;
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = i16;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<i16, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<String, DayError> {
+        Ok(part2(data))
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Instruction {
+pub enum Instruction {
     NoOp,
     AddX(i16),
 }
@@ -41,22 +51,26 @@ impl Instruction {
     }
 }
 
-fn parse(input: &str) -> Result<Vec<Instruction>> {
+pub type Parsed = Vec<Instruction>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
     input
         .lines()
-        .map(|l| {
-            if l == "noop" {
-                Ok(Instruction::NoOp)
-            } else if let Some(("addx", val)) = l.split_once(' ') {
-                Ok(Instruction::AddX(val.parse()?))
-            } else {
-                Err(eyre!("Invalid instruction"))
-            }
-        })
+        .map(|l| Ok(parse_instruction(input, l)?))
         .collect()
 }
 
-fn part1(instrs: &[Instruction]) -> i16 {
+fn parse_instruction(input: &str, line: &str) -> Result<Instruction, ParseError> {
+    if line == "noop" {
+        Ok(Instruction::NoOp)
+    } else if let Some(("addx", val)) = line.split_once(' ') {
+        Ok(Instruction::AddX(parse_at(input, val, "a number")?))
+    } else {
+        Err(ParseError::at(input, line, "`noop` or `addx <value>`"))
+    }
+}
+
+pub fn part1(instrs: &[Instruction]) -> i16 {
     let mut cycle = 0;
     let mut ans = 0;
 
@@ -91,7 +105,7 @@ fn part1(instrs: &[Instruction]) -> i16 {
     ans
 }
 
-fn part2(instrs: &[Instruction]) -> String {
+pub fn part2(instrs: &[Instruction]) -> String {
     let mut cycle: i16 = 0;
     let mut ans = String::with_capacity(40 * 7);
 
@@ -129,22 +143,76 @@ fn part2(instrs: &[Instruction]) -> String {
     ans
 }
 
+const SCREEN_WIDTH: usize = 40;
+const SCREEN_HEIGHT: usize = 6;
+
+/// Solves both parts from a reader, an instruction at a time.
+///
+/// The drawing is kept on a screen of 40 by 6 pixels, which wraps back to the top after 240
+/// cycles and draws over what was there, so memory doesn't grow with the input. Inputs of up to
+/// 240 cycles, as the puzzle's are, draw the same as [`part2`].
+pub fn stream(reader: impl BufRead) -> Result<(i16, String), DayError> {
+    let mut cycle: u64 = 0;
+    let mut x: i16 = 1;
+    let mut strength = 0;
+    let mut screen = [[false; SCREEN_WIDTH]; SCREEN_HEIGHT];
+    input::for_each_line(reader, |line| {
+        let instr = parse_instruction(line, line)?;
+        for _ in 0..instr.cycle_len() {
+            let column = (cycle % SCREEN_WIDTH as u64) as usize;
+            let row = (cycle / SCREEN_WIDTH as u64 % SCREEN_HEIGHT as u64) as usize;
+            screen[row][column] = (column as i16 - x).abs() <= 1;
+
+            cycle += 1;
+            if cycle <= 220 && cycle % 40 == 20 {
+                strength += x * cycle as i16;
+            }
+        }
+
+        if let Instruction::AddX(val) = instr {
+            x += val;
+        }
+        Ok(())
+    })?;
+
+    let drawn = cycle.min((SCREEN_WIDTH * SCREEN_HEIGHT) as u64) as usize;
+    let mut drawing = String::with_capacity(drawn + SCREEN_HEIGHT);
+    for (idx, &lit) in screen.iter().flatten().take(drawn).enumerate() {
+        if idx % SCREEN_WIDTH == 0 {
+            drawing.push('\n');
+        }
+        drawing.push(if lit { '#' } else { '.' });
+    }
+
+    Ok((strength, drawing))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use aoc_lib::Example;
 
     #[test]
-    fn part1_test() {
+    fn stream_test() {
         let data = aoc_lib::input(DAY.day)
             .example(Example::Part1, 1)
             .open()
             .unwrap();
-
         let instrs = parse(&data).unwrap();
-        let expected = 13140;
-        let actual = part1(&instrs);
 
-        assert_eq!(expected, actual);
+        let expected = (part1(&instrs), part2(&instrs));
+        assert_eq!(expected, stream(data.as_bytes()).unwrap());
+
+        // Running the program twice draws the second pass over the first, so the screen ends up
+        // with the last six rows of the full drawing.
+        let twice = format!("{data}{data}");
+        let full = part2(&parse(&twice).unwrap());
+        let last_rows: String = full
+            .split('\n')
+            .skip(7)
+            .map(|row| format!("\n{row}"))
+            .collect();
+        let (_, drawing) = stream(twice.as_bytes()).unwrap();
+        assert_eq!(last_rows, drawing);
     }
 }
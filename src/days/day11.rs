@@ -1,32 +1,39 @@
-use aoc_lib::{misc::Top, Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{eyre::eyre, Report, Result};
+use aoc_lib::{misc::Top, Day};
+use color_eyre::Result;
+
+use crate::{
+    input::{parse_at, ParseError},
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
+};
 
 // 11:48
 // 12:56
 
-pub const DAY: Day = Day {
-    day: 11,
-    name: "Monkey in the Middle",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[("Parse", run_parse)],
-};
+pub const DAY: Day = solution::day::<Day11>();
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(solve::<false>(data.clone())))
-}
+pub struct Day11;
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(solve::<true>(data.clone())))
-}
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const NAME: &'static str = "Monkey in the Middle";
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part2(data))
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -60,66 +67,61 @@ impl Operation {
 }
 
 #[derive(Debug, Clone)]
-struct Monkey {
+pub struct Monkey {
     items: Vec<u64>,
     operation: Operation,
     test_div: u64,
     paths: [usize; 2],
 }
 
-fn parse(input: &str) -> Result<Vec<Monkey>> {
+pub type Parsed = Vec<Monkey>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
     let mut monkeys = Vec::new();
 
     for monkey_segment in input.split("\n\n") {
         let mut lines = monkey_segment.lines();
         let _ = lines.next(); // Monkey ID
 
-        let Some(items_str) = lines.next().and_then(|s| s.strip_prefix("  Starting items: ")) else {
-            return Err(eyre!("Invalid monkey"));
+        // Takes the next line of the monkey's notes, without the label at the start.
+        let mut field = |label: &str, expected: &str| {
+            let line = lines
+                .next()
+                .unwrap_or(&monkey_segment[monkey_segment.len()..]);
+            line.strip_prefix(label)
+                .ok_or_else(|| ParseError::at(input, line, expected))
         };
+
+        let items_str = field("  Starting items: ", "the starting items")?;
         let items: Vec<_> = items_str
             .split(", ")
-            .map(str::parse::<u64>)
+            .map(|item| parse_at::<u64>(input, item, "a worry level"))
             .collect::<Result<_, _>>()?;
 
-        let Some(operation) = lines.next().and_then(|s| {
-            let s = s.strip_prefix("  Operation: new = old ")?;
-            let mut parts = s.split(' ');
-            let op = parts.next()?;
-            let rhs = parts.next()?;
-
-            let rhs = match rhs {
-                "old" => OpRhs::Old,
-                _ => OpRhs::Literal(rhs.parse().ok()?)
-            };
-
-            let op = match op {
-                "*" => Operation::Mul(rhs),
-                "+" => Operation::Add(rhs),
-                _ => return None,
-            };
-            Some(op)
-        }) else {
-            return Err(eyre!("invalid monkey"));
+        let operation_str = field("  Operation: new = old ", "the operation")?;
+        let Some((op, rhs)) = operation_str.split_once(' ') else {
+            return Err(ParseError::at(input, operation_str, "an operator and a value").into());
         };
 
-        let Some(test_div) = lines.next()
-            .and_then(|s| s.strip_prefix("  Test: divisible by "))
-            .and_then(|s| s.parse::<u64>().ok()) else {
-            return Err(eyre!("Invalid monkey"));
+        let rhs = match rhs {
+            "old" => OpRhs::Old,
+            _ => OpRhs::Literal(parse_at(input, rhs, "`old` or a number")?),
         };
 
-        let Some(true_path) = lines.next()
-            .and_then(|s| s.strip_prefix("    If true: throw to monkey "))
-            .and_then(|s| s.parse::<usize>().ok()) else {
-                return Err(eyre!("Invalid monkey"));
+        let operation = match op {
+            "*" => Operation::Mul(rhs),
+            "+" => Operation::Add(rhs),
+            _ => return Err(ParseError::at(input, op, "`*` or `+`").into()),
         };
 
-        let Some(false_path) = lines.next()
-            .and_then(|s| s.strip_prefix("    If false: throw to monkey "))
-            .and_then(|s| s.parse::<usize>().ok()) else {
-                return Err(eyre!("Invalid monkey"));
-        };
+        let test_div = field("  Test: divisible by ", "the test")?;
+        let test_div = parse_at(input, test_div, "a divisor")?;
+
+        let true_path = field("    If true: throw to monkey ", "where to throw if true")?;
+        let true_path = parse_at(input, true_path, "a monkey number")?;
+
+        let false_path = field("    If false: throw to monkey ", "where to throw if false")?;
+        let false_path = parse_at(input, false_path, "a monkey number")?;
 
         let monkey = Monkey {
             items,
@@ -134,6 +136,14 @@ fn parse(input: &str) -> Result<Vec<Monkey>> {
     Ok(monkeys)
 }
 
+pub fn part1(monkeys: &[Monkey]) -> usize {
+    solve::<false>(monkeys.to_vec())
+}
+
+pub fn part2(monkeys: &[Monkey]) -> usize {
+    solve::<true>(monkeys.to_vec())
+}
+
 fn solve<const PART2: bool>(mut monkeys: Vec<Monkey>) -> usize {
     let mut inspect_counts = vec![0; monkeys.len()];
 
@@ -173,37 +183,3 @@ fn solve<const PART2: bool>(mut monkeys: Vec<Monkey>) -> usize {
     let [a, b] = top2.0;
     a * b
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc_lib::Example;
-
-    #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let monkeys = parse(&data).unwrap();
-        let expected = 10605;
-        let actual = solve::<false>(monkeys);
-
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn part2_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let monkeys = parse(&data).unwrap();
-        let expected = 2_713_310_158;
-        let actual = solve::<true>(monkeys);
-
-        assert_eq!(expected, actual);
-    }
-}
@@ -1,290 +1,126 @@
-use std::collections::BinaryHeap;
-
-use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{eyre::eyre, Report, Result};
+use aoc_lib::Day;
+use color_eyre::{eyre::eyre, Result};
+
+use crate::{
+    geometry::Point2,
+    grid::Grid,
+    input::Shape,
+    search,
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
+};
 
 // 10:21
 // 11:39
 
-pub const DAY: Day = Day {
-    day: 12,
-    name: "Hill Climbing Algorithm",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[("Parse", run_parse)],
-};
+pub const DAY: Day = solution::day::<Day12>();
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(path_search_part1(&data)))
-}
+pub struct Day12;
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(path_search_part2(&data)))
-}
-
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
-}
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const NAME: &'static str = "Hill Climbing Algorithm";
+    const INPUT: Shape = Shape::Grid;
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
-struct Point {
-    x: u8,
-    y: u8,
-}
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-impl Point {
-    fn new(x: u8, y: u8) -> Self {
-        Self { x, y }
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
     }
 
-    fn to_idx(self, width: usize) -> usize {
-        self.y as usize * width + self.x as usize
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        part1(data)
     }
 
-    fn neighbours(self) -> [Point; 4] {
-        let Point { x, y } = self;
-        [
-            Point::new(x, y.wrapping_sub(1)),
-            Point::new(x.wrapping_sub(1), y),
-            Point::new(x + 1, y),
-            Point::new(x, y + 1),
-        ]
+    fn part2(data: &Parsed) -> Result<usize, DayError> {
+        part2(data)
     }
+}
 
-    fn cost_to(self, to: Point) -> u16 {
-        let (max_x, min_x) = (self.x.max(to.x), self.x.min(to.x));
-        let (max_y, min_y) = (self.y.max(to.y), self.y.min(to.y));
+type Point = Point2<usize>;
 
-        (max_x - min_x) as u16 + (max_y - min_y) as u16
-    }
+fn cell(point: Point) -> (usize, usize) {
+    (point.x, point.y)
 }
 
 #[derive(Debug)]
-struct Map {
-    tiles: Vec<u8>,
-    width: usize,
-    height: usize,
+pub struct Map {
+    tiles: Grid<u8>,
     start: Point,
     end: Point,
 }
 
 impl Map {
-    fn contains(&self, point: Point) -> bool {
-        (0..self.width as u8).contains(&point.x) && (0..self.height as u8).contains(&point.y)
-    }
-
-    fn can_traverse(&self, from: Point, to: Point) -> bool {
-        if !self.contains(from) || !self.contains(to) {
-            return false;
-        }
-
-        let from_tile = self.tiles[from.to_idx(self.width)];
-        let to_tile = self.tiles[to.to_idx(self.width)];
-
-        to_tile < from_tile || to_tile - from_tile <= 1
+    /// The squares next to `point` which are on the map, and pass the `climbable` check from
+    /// `point`'s height to theirs.
+    fn neighbours(
+        &self,
+        point: Point,
+        climbable: fn(u8, u8) -> bool,
+    ) -> impl Iterator<Item = Point> + '_ {
+        let height = self.tiles[cell(point)];
+        self.tiles
+            .neighbours4(point.x, point.y)
+            .filter(move |&pos| climbable(height, self.tiles[pos]))
+            .map(|(x, y)| Point::new(x, y))
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq)]
-struct State {
-    heuristic_cost: u16,
-    cost: u16,
-    pos: Point,
+/// Whether we can step from a square of height `from` to one of height `to`.
+fn can_climb(from: u8, to: u8) -> bool {
+    to < from || to - from <= 1
 }
 
-impl PartialEq for State {
-    fn eq(&self, other: &Self) -> bool {
-        self.heuristic_cost == other.heuristic_cost
-    }
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.heuristic_cost.cmp(&self.heuristic_cost)
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
+pub type Parsed = Map;
 
-fn parse(input: &str) -> Result<Map> {
-    let mut tiles = Vec::new();
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
     let mut start = Point::new(0, 0);
     let mut end = Point::new(0, 0);
-    let mut width = 0;
-    let mut height = 0;
 
-    for byte in input.trim().bytes() {
-        match byte {
-            b'a'..=b'z' => tiles.push(byte - b'a'),
-            b'\r' => continue,
-            b'\n' => {
-                width = 0;
-                height += 1;
-                continue;
-            }
+    let tiles = Grid::parse(
+        input,
+        "a height from `a` to `z`, `S` or `E`",
+        |(x, y), b| match b {
+            b'a'..=b'z' => Some(b - b'a'),
             b'S' => {
-                tiles.push(0);
-                start = Point::new(width as u8, height as u8);
+                start = Point::new(x, y);
+                Some(0)
             }
             b'E' => {
-                tiles.push(25);
-                end = Point::new(width as u8, height as u8);
-            }
-            _ => return Err(eyre!("Invalid character: `{}`", byte as char)),
-        }
-        width += 1;
-    }
-    height += 1;
-
-    assert_eq!(width * height, tiles.len(),);
-
-    Ok(Map {
-        tiles,
-        width,
-        height,
-        start,
-        end,
-    })
-}
-
-fn path_search_part1(map: &Map) -> u32 {
-    let mut queue = BinaryHeap::new();
-    let mut dist = vec![u16::MAX; map.width * map.height];
-    let mut prev = vec![Point::new(255, 255); map.width * map.height];
-
-    dist[map.start.to_idx(map.width)] = 0;
-    queue.push(State {
-        heuristic_cost: 0,
-        cost: 0,
-        pos: map.start,
-    });
-
-    while let Some(next) = queue.pop() {
-        for neighbour in next.pos.neighbours() {
-            if !map.contains(neighbour) {
-                continue;
-            }
-            if !map.can_traverse(next.pos, neighbour) {
-                continue;
+                end = Point::new(x, y);
+                Some(25)
             }
-            let total_cost = next.cost + 1;
-            if neighbour == map.end {
-                prev[map.end.to_idx(map.width)] = next.pos;
-                break;
-            }
-            let nidx = neighbour.to_idx(map.width);
-            if total_cost < dist[nidx] {
-                dist[nidx] = total_cost;
-                prev[nidx] = next.pos;
-                queue.push(State {
-                    heuristic_cost: total_cost + neighbour.cost_to(map.end),
-                    cost: total_cost,
-                    pos: neighbour,
-                });
-            }
-        }
-    }
+            _ => None,
+        },
+    )?;
 
-    let mut steps = 0;
-    let mut cur_pos = map.end;
-    while let Some(prev) = prev.get(cur_pos.to_idx(map.width)) {
-        steps += 1;
-        cur_pos = *prev;
-    }
-
-    steps - 1
+    Ok(Map { tiles, start, end })
 }
 
-fn path_search_part2(map: &Map) -> u32 {
-    let mut queue = BinaryHeap::new();
-    let mut dist = vec![u16::MAX; map.width * map.height];
-    let mut prev = vec![Point::new(255, 255); map.width * map.height];
-
-    dist[map.end.to_idx(map.width)] = 0;
-    queue.push(State {
-        heuristic_cost: 0,
-        cost: 0,
-        pos: map.end,
-    });
-
-    let mut last_tile = map.start;
-
-    while let Some(next) = queue.pop() {
-        for neighbour in next.pos.neighbours() {
-            if !map.contains(neighbour) {
-                continue;
-            }
-            if !map.can_traverse(neighbour, next.pos) {
-                continue;
-            }
-            let total_cost = next.cost + 1;
-            let nidx = neighbour.to_idx(map.width);
-
-            if total_cost < dist[nidx] {
-                dist[nidx] = total_cost;
-                prev[nidx] = next.pos;
-                queue.push(State {
-                    heuristic_cost: total_cost,
-                    cost: total_cost,
-                    pos: neighbour,
-                });
-            }
-            if map.tiles[nidx] == 0 && dist[nidx] < dist[last_tile.to_idx(map.width)] {
-                prev[nidx] = next.pos;
-                last_tile = neighbour;
-            }
-        }
-    }
-
-    let mut steps = 0;
-    let mut cur_pos = last_tile;
-    while let Some(prev) = prev.get(cur_pos.to_idx(map.width)) {
-        steps += 1;
-        cur_pos = *prev;
-    }
-
-    steps - 1
+pub fn part1(map: &Map) -> Result<usize> {
+    let path = search::astar(
+        [map.start],
+        |&pos, _| map.neighbours(pos, can_climb),
+        |_, _| 1,
+        |&pos| pos.manhattan(map.end),
+        |&pos| pos == map.end,
+    );
+
+    path.map(|p| p.cost)
+        .ok_or_else(|| eyre!("No path from the start to the end"))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc_lib::Example;
+pub fn part2(map: &Map) -> Result<usize> {
+    // Search backwards from the end for the nearest of all the lowest tiles.
+    let path = search::bfs(
+        [map.end],
+        |&pos, _| map.neighbours(pos, |from, to| can_climb(to, from)),
+        |&pos| map.tiles[cell(pos)] == 0,
+    );
 
-    #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let map = parse(&data).unwrap();
-        let expected = 31;
-        let actual = path_search_part1(&map);
-
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn part2_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let map = parse(&data).unwrap();
-        let expected = 29;
-        let actual = path_search_part2(&map);
-
-        assert_eq!(expected, actual);
-    }
+    path.map(|p| p.cost)
+        .ok_or_else(|| eyre!("No path from any square at elevation `a` to the end"))
 }
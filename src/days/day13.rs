@@ -1,34 +1,41 @@
 use std::{cmp::Ordering, iter::Peekable};
 
-use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{eyre::eyre, Report, Result};
+use aoc_lib::Day;
+use color_eyre::Result;
+
+use crate::{
+    input::{parse_at, ParseError},
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
+};
 
 // 12:25
 // 15:02
 
-pub const DAY: Day = Day {
-    day: 13,
-    name: "Distress Signal",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[("Parse", run_parse)],
-};
+pub const DAY: Day = solution::day::<Day13>();
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1(&data)))
-}
+pub struct Day13;
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part2(&data)))
-}
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const NAME: &'static str = "Distress Signal";
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part1(data))
+    }
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
+    fn part2(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part2(data))
+    }
 }
 
 #[derive(Debug, Clone, Eq)]
@@ -81,12 +88,19 @@ impl PartialOrd for PacketContent {
 }
 
 #[derive(Debug, Clone)]
-struct PacketPair {
+pub struct PacketPair {
     left: PacketContent,
     right: PacketContent,
 }
 
+/// Points an error at the character starting at `idx` in `packet`, or the end of the packet.
+fn error_at(input: &str, packet: &str, idx: usize, expected: &str) -> ParseError {
+    let len = packet[idx..].chars().next().map_or(0, char::len_utf8);
+    ParseError::at(input, &packet[idx..idx + len], expected)
+}
+
 fn parse_item(
+    input: &str,
     packet: &str,
     chars: &mut Peekable<impl Iterator<Item = (usize, u8)>>,
 ) -> Result<PacketContent> {
@@ -94,7 +108,7 @@ fn parse_item(
         // Our item is a list.
         Some((idx, b'[')) => {
             let start = idx + 1;
-            let mut end_idx = start + 1;
+            let mut end_idx = None;
             let mut depth = 1;
             // Search for matching bracket.
             for (idx, ch) in chars.by_ref() {
@@ -104,11 +118,15 @@ fn parse_item(
                     depth -= 1;
                 }
                 if depth == 0 {
-                    end_idx = idx;
+                    end_idx = Some(idx);
                     break;
                 }
             }
 
+            let Some(end_idx) = end_idx else {
+                return Err(error_at(input, packet, packet.len(), "`]`").into());
+            };
+
             // Now we know where the end of our list is, we substring only that part of our
             // input, not including surrounding brackets.
             let mut list = Vec::new();
@@ -117,7 +135,7 @@ fn parse_item(
 
             // Parse items out of our substring.
             while item_iter.peek().is_some() {
-                let next_item = parse_item(item_str, &mut item_iter)?;
+                let next_item = parse_item(input, item_str, &mut item_iter)?;
                 list.push(next_item);
 
                 match item_iter.peek() {
@@ -125,8 +143,8 @@ fn parse_item(
                     Some((_, b',')) => {
                         item_iter.next();
                     }
-                    Some((_, ch)) => {
-                        return Err(eyre!("Unexpected character: `{ch}`"));
+                    Some(&(idx, _)) => {
+                        return Err(error_at(input, item_str, idx, "`,` or `]`").into());
                     }
                 }
             }
@@ -141,24 +159,28 @@ fn parse_item(
             }
 
             let num_str = &packet[idx..end_idx];
-            Ok(PacketContent::Integer(num_str.parse()?))
+            Ok(PacketContent::Integer(parse_at(
+                input, num_str, "a number",
+            )?))
         }
 
-        Some((_, ch)) => Err(eyre!("Unexpected character: `{ch}`")),
-        _ => Err(eyre!("Unexpected EOL")),
+        Some((idx, _)) => Err(error_at(input, packet, idx, "`[` or a number").into()),
+        None => Err(error_at(input, packet, packet.len(), "`[` or a number").into()),
     }
 }
 
-fn parse(input: &str) -> Result<Vec<PacketPair>> {
+pub type Parsed = Vec<PacketPair>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
     let mut pairs = Vec::new();
 
     for line_pair in input.trim().split("\n\n") {
         let Some((left, right)) = line_pair.split_once('\n') else {
-            return Err(eyre!("Invalid packet"));
+            return Err(ParseError::at(input, line_pair, "a pair of packets").into());
         };
 
-        let left = parse_item(left, &mut left.bytes().enumerate().peekable())?;
-        let right = parse_item(right, &mut right.bytes().enumerate().peekable())?;
+        let left = parse_item(input, left, &mut left.bytes().enumerate().peekable())?;
+        let right = parse_item(input, right, &mut right.bytes().enumerate().peekable())?;
 
         pairs.push(PacketPair { left, right })
     }
@@ -166,7 +188,7 @@ fn parse(input: &str) -> Result<Vec<PacketPair>> {
     Ok(pairs)
 }
 
-fn part1(data: &[PacketPair]) -> usize {
+pub fn part1(data: &[PacketPair]) -> usize {
     data.iter()
         .enumerate()
         .filter(|(_, pair)| pair.left < pair.right)
@@ -174,7 +196,7 @@ fn part1(data: &[PacketPair]) -> usize {
         .sum()
 }
 
-fn part2(data: &[PacketPair]) -> usize {
+pub fn part2(data: &[PacketPair]) -> usize {
     let divider = parse("[[2]]\n[[6]]").unwrap();
     let PacketPair { left, right } = &divider[0];
 
@@ -205,33 +227,16 @@ fn part2(data: &[PacketPair]) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc_lib::Example;
-
-    #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let packets = parse(&data).unwrap();
-        let expected = 13;
-        let actual = part1(&packets);
-
-        assert_eq!(expected, actual);
-    }
 
     #[test]
-    fn part2_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
+    fn unclosed_list_test() {
+        let data = "[1,[2,3]\n[1]\n";
 
-        let packets = parse(&data).unwrap();
-        let expected = 140;
-        let actual = part2(&packets);
+        let error = parse(data).unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
 
-        assert_eq!(expected, actual);
+        assert_eq!(1, error.line);
+        assert_eq!(9, error.column);
+        assert_eq!("`]`", error.expected);
     }
 }
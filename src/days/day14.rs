@@ -1,47 +1,50 @@
-use std::{num::ParseIntError, ops::RangeInclusive};
+use std::ops::RangeInclusive;
 
-use aoc_lib::{misc::ResultZip, Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{Report, Result};
+use aoc_lib::Day;
+use color_eyre::Result;
 use itertools::Itertools;
 
+use crate::{
+    geometry::{BoundingBox, Point2},
+    grid::Grid,
+    input::{parse_at, ParseError},
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
+};
+
 // 10:59
 // 13:04
 
-pub const DAY: Day = Day {
-    day: 14,
-    name: "Regolith Reservoir",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[("Parse", run_parse)],
-};
+pub const DAY: Day = solution::day::<Day14>();
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(do_fall(&data)))
-}
+pub struct Day14;
 
-#[allow(unused)]
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let mut data = parse(input).map_err(UserError)?;
-    insert_floor(&mut data);
-    b.bench(|| Ok::<_, NoError>(do_fall(&data)))
-}
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const NAME: &'static str = "Regolith Reservoir";
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
-}
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Point {
-    x: u16,
-    y: u16,
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part2(data))
+    }
 }
 
+type Point = Point2<u16>;
+
 #[derive(Debug, Clone, Copy)]
-struct Line {
+pub struct Line {
     start: Point,
     end: Point,
 }
@@ -56,14 +59,20 @@ impl Line {
     }
 }
 
-fn build_blocked_map(lines: &[Line]) -> (u16, usize, u16, Vec<bool>) {
-    let floor_height = lines.iter().max_by_key(|l| l.end.y).copied().unwrap().end.y + 2;
+/// The floor is two below the lowest rock.
+fn floor_height(lines: &[Line]) -> u16 {
+    let bounds = BoundingBox::from_points(lines.iter().flat_map(|l| [l.start, l.end]));
+    bounds.map_or(0, |b| b.max.y) + 2
+}
+
+fn build_blocked_map(lines: &[Line]) -> (u16, u16, Grid<bool>) {
+    let floor_height = floor_height(lines);
     let height = floor_height as usize + 1;
     let left = 500 - (floor_height + 10);
     let right = 500 + floor_height + 10;
     let width = (right - left) as usize;
 
-    let mut blocked = vec![false; width * height];
+    let mut blocked = Grid::new(width, height, false);
 
     for line in lines {
         if line.start.x == line.end.x {
@@ -71,33 +80,41 @@ fn build_blocked_map(lines: &[Line]) -> (u16, usize, u16, Vec<bool>) {
             let start = line.start.y.min(line.end.y);
             let end = line.start.y.max(line.end.y);
             for y in start..=end {
-                blocked[y as usize * width + (line.start.x - left) as usize] = true;
+                blocked[((line.start.x - left) as usize, y as usize)] = true;
             }
         } else {
             // Horizontal line
             let start = line.start.x.min(line.end.x);
             let end = line.start.x.max(line.end.x);
             for x in start..=end {
-                blocked[line.start.y as usize * width + (x - left) as usize] = true;
+                blocked[((x - left) as usize, line.start.y as usize)] = true;
             }
         }
     }
 
-    (left, width, floor_height, blocked)
+    (left, floor_height, blocked)
 }
 
-fn parse(input: &str) -> Result<Vec<Line>> {
+pub type Parsed = Vec<Line>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
     let mut lines = Vec::new();
 
     for line in input.lines() {
         let coords = line
             .split(" -> ")
-            .flat_map(|pair| pair.split_once(','))
-            .map(|(x, y)| -> Result<Point, ParseIntError> {
-                x.parse().zip(y.parse()).map(|(x, y)| Point { x, y })
+            .map(|pair| -> Result<Point, ParseError> {
+                let Some((x, y)) = pair.split_once(',') else {
+                    return Err(ParseError::at(input, pair, "a point like `498,4`"));
+                };
+
+                Ok(Point {
+                    x: parse_at(input, x, "an x coordinate")?,
+                    y: parse_at(input, y, "a y coordinate")?,
+                })
             })
             .tuple_windows()
-            .map(|(start, end)| -> Result<Line, ParseIntError> {
+            .map(|(start, end)| -> Result<Line, ParseError> {
                 let start = start?;
                 let end = end?;
 
@@ -131,7 +148,7 @@ fn parse(input: &str) -> Result<Vec<Line>> {
 
 #[allow(unused)]
 fn insert_floor(lines: &mut Vec<Line>) {
-    let floor_height = lines.iter().max_by_key(|l| l.end.y).copied().unwrap().end.y + 2;
+    let floor_height = floor_height(lines);
 
     lines.push(Line {
         start: Point {
@@ -145,9 +162,19 @@ fn insert_floor(lines: &mut Vec<Line>) {
     });
 }
 
+pub fn part1(lines: &[Line]) -> usize {
+    do_fall(lines)
+}
+
+pub fn part2(lines: &[Line]) -> usize {
+    let mut lines = lines.to_vec();
+    insert_floor(&mut lines);
+    do_fall(&lines)
+}
+
 fn do_fall(lines: &[Line]) -> usize {
-    let (left_bound, width, floor_height, mut blocked) = build_blocked_map(lines);
-    let to_idx = |x: u16, y: u16| y as usize * width + (x - left_bound) as usize;
+    let (left_bound, floor_height, mut blocked) = build_blocked_map(lines);
+    let cell = |x: u16, y: u16| ((x - left_bound) as usize, y as usize);
 
     let mut num_sand = 0;
     let mut position_stack = Vec::new();
@@ -155,7 +182,7 @@ fn do_fall(lines: &[Line]) -> usize {
         let mut sand = position_stack.pop().unwrap_or(Point { x: 500, y: 0 });
 
         // We're full!;
-        if blocked[to_idx(sand.x, sand.y)] {
+        if blocked[cell(sand.x, sand.y)] {
             break;
         }
 
@@ -163,14 +190,14 @@ fn do_fall(lines: &[Line]) -> usize {
             // We've found an intersecting line, but we don't know if there's any resting sand between it and us.
             // We need to search.
             let blocking_sand = (sand.y + 1..=floor_height)
-                .map(|y| (y, blocked[to_idx(sand.x, y)]))
+                .map(|y| (y, blocked[cell(sand.x, y)]))
                 .find(|(_, pb)| *pb);
 
             if let Some((blocked_y, _)) = blocking_sand {
                 // Check if we fall to either side.
 
-                let lower_left = to_idx(sand.x - 1, blocked_y);
-                let lower_right = to_idx(sand.x + 1, blocked_y);
+                let lower_left = cell(sand.x - 1, blocked_y);
+                let lower_right = cell(sand.x + 1, blocked_y);
                 if !blocked[lower_left] {
                     position_stack.push(sand);
                     sand.x -= 1;
@@ -182,7 +209,7 @@ fn do_fall(lines: &[Line]) -> usize {
                 } else {
                     // We can't fall further. Come to rest.
                     num_sand += 1;
-                    blocked[to_idx(sand.x, blocked_y - 1)] = true;
+                    blocked[cell(sand.x, blocked_y - 1)] = true;
                     continue 'outer;
                 }
             } else {
@@ -195,59 +222,23 @@ fn do_fall(lines: &[Line]) -> usize {
 }
 
 #[allow(unused)]
-fn draw_map(lines: &[Line], sand: &[bool], end_y: u16, x_start: u16, x_end: u16) {
-    let x_range = (x_end - x_start) as usize;
-    let mut map: Vec<char> = sand.iter().map(|b| if *b { 'o' } else { '.' }).collect();
+fn draw_map(lines: &[Line], sand: &Grid<bool>, x_start: u16) {
+    let mut map = Grid::new(sand.width(), sand.height(), '.');
+    for (pos, _) in sand.iter().filter(|(_, s)| **s) {
+        map[pos] = 'o';
+    }
 
     for line in lines {
         if line.start.y == line.end.y {
             for x in line.x_range() {
-                map[line.start.y as usize * x_range + (x - x_start) as usize] = '#';
+                map[((x - x_start) as usize, line.start.y as usize)] = '#';
             }
         } else {
             for y in line.y_range() {
-                map[y as usize * x_range + (line.start.x - x_start) as usize] = '#';
+                map[((line.start.x - x_start) as usize, y as usize)] = '#';
             }
         }
     }
 
-    for row in map.chunks_exact(x_range) {
-        row.iter().for_each(|c| eprint!("{c}"));
-        eprintln!();
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc_lib::Example;
-
-    #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let lines = parse(&data).unwrap();
-        let expected = 24;
-        let actual = do_fall(&lines);
-
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn part2_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let mut lines = parse(&data).unwrap();
-        insert_floor(&mut lines);
-        let expected = 93;
-        let actual = do_fall(&lines);
-
-        assert_eq!(expected, actual);
-    }
+    eprintln!("{map}");
 }
@@ -1,49 +1,71 @@
 use std::{collections::HashSet, ops::RangeInclusive};
 
-use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{eyre::eyre, Report, Result};
+use aoc_lib::Day;
+use color_eyre::{eyre::eyre, Result};
+
+use crate::{
+    geometry::Point2,
+    input::{parse_at, ParseError},
+    solution::{self, run_parse, Params, Runner, Solution},
+    DayError,
+};
 
 // 12:10
 // 13:15
 
-pub const DAY: Day = Day {
-    day: 15,
-    name: "Beacon Exclusion Zone",
-    part_1: run_part1,
-    part_2: Some(run_part2),
-    other: &[("Parse", run_parse)],
-};
+pub const DAY: Day = solution::day::<Day15>();
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let (sensors, beacons) = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1::<2_000_000>(&sensors, &beacons)))
-}
+pub struct Day15;
 
-fn run_part2(input: &str, b: Bench) -> BenchResult {
-    let (sensors, _) = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part2::<4_000_000>(&sensors)))
-}
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const NAME: &'static str = "Beacon Exclusion Zone";
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
-}
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part1(data, ROW))
+    }
+
+    fn part2(data: &Parsed) -> Result<i64, DayError> {
+        part2(data, SEARCH_BOUND)
+    }
+
+    fn part1_with(data: &Parsed, params: &Params) -> Result<usize, DayError> {
+        Ok(part1(data, solution::param(params, "row")?))
+    }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
+    fn part2_with(data: &Parsed, params: &Params) -> Result<i64, DayError> {
+        part2(data, solution::param(params, "bound")?)
+    }
 }
 
+/// The row part 1 asks about in the real input. The example uses row 10, given to
+/// [`Solution::part1_with`] as `row`.
+pub const ROW: i32 = 2_000_000;
+
+/// The largest x and y part 2 searches up to in the real input. The example uses 20, given to
+/// [`Solution::part2_with`] as `bound`.
+pub const SEARCH_BOUND: i32 = 4_000_000;
+
+pub type Point = Point2<i32>;
+
 #[derive(Debug)]
-struct Sensor {
+pub struct Sensor {
     pos: Point,
     closest_beacon: Point,
 }
 
-fn parse(input: &str) -> Result<(Vec<Sensor>, Vec<Point>)> {
+pub type Parsed = (Vec<Sensor>, Vec<Point>);
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
     let mut sensors = Vec::new();
     let mut beacons = HashSet::new();
 
@@ -58,18 +80,19 @@ fn parse(input: &str) -> Result<(Vec<Sensor>, Vec<Point>)> {
 
     for line in input.lines().map(str::trim) {
         let Some((sx, sy, bx, by)) = split_line(line) else {
-            return Err(eyre!("Invalid line: `{}`", line));
+            let expected = "a line like `Sensor at x=2, y=18: closest beacon is at x=-2, y=15`";
+            return Err(ParseError::at(input, line, expected).into());
         };
 
         let closest_beacon = Point {
-            x: bx.parse()?,
-            y: by.parse()?,
+            x: parse_at(input, bx, "an x coordinate")?,
+            y: parse_at(input, by, "a y coordinate")?,
         };
 
         sensors.push(Sensor {
             pos: Point {
-                x: sx.parse()?,
-                y: sy.parse()?,
+                x: parse_at(input, sx, "an x coordinate")?,
+                y: parse_at(input, sy, "a y coordinate")?,
             },
             closest_beacon,
         });
@@ -81,9 +104,7 @@ fn parse(input: &str) -> Result<(Vec<Sensor>, Vec<Point>)> {
 }
 
 fn get_x_range(sensor: &Sensor, row: i32) -> Option<RangeInclusive<i32>> {
-    let deltay = (sensor.pos.y - sensor.closest_beacon.y).abs();
-    let deltax = (sensor.pos.x - sensor.closest_beacon.x).abs();
-    let range = deltay + deltax;
+    let range = sensor.pos.manhattan(sensor.closest_beacon);
     let y_range = (sensor.pos.y - range)..=(sensor.pos.y + range);
     if !y_range.contains(&row) {
         return None;
@@ -93,18 +114,18 @@ fn get_x_range(sensor: &Sensor, row: i32) -> Option<RangeInclusive<i32>> {
     Some(x_range)
 }
 
-fn part1<const ROW: i32>(sensors: &[Sensor], beacons: &[Point]) -> usize {
+fn covered_in_row(sensors: &[Sensor], beacons: &[Point], row: i32) -> usize {
     let mut ranges = Vec::new();
 
     for sensor in sensors {
-        let Some(x_range) = get_x_range(sensor, ROW) else { continue };
+        let Some(x_range) = get_x_range(sensor, row) else { continue };
         ranges.push(x_range);
     }
 
     merge_ranges(&mut ranges, &mut Vec::new());
     let mut count = 0;
 
-    let row_beacons: Vec<Point> = beacons.iter().filter(|b| b.y == ROW).copied().collect();
+    let row_beacons: Vec<Point> = beacons.iter().filter(|b| b.y == row).copied().collect();
 
     for range in ranges {
         for x in range {
@@ -118,9 +139,20 @@ fn part1<const ROW: i32>(sensors: &[Sensor], beacons: &[Point]) -> usize {
     count
 }
 
+/// Counts the positions in `row` where there can't be a beacon.
+pub fn part1((sensors, beacons): &Parsed, row: i32) -> usize {
+    covered_in_row(sensors, beacons, row)
+}
+
+/// Finds the tuning frequency of the one position no sensor covers, with x and y from 0 to
+/// `bound`.
+pub fn part2((sensors, _): &Parsed, bound: i32) -> Result<i64> {
+    tuning_frequency(sensors, bound)
+}
+
 fn merge_ranges(ranges: &mut Vec<RangeInclusive<i32>>, scratch: &mut Vec<RangeInclusive<i32>>) {
     ranges.sort_unstable_by(|a, b| a.end().cmp(b.end()));
-    let mut cur_range = ranges.pop().unwrap();
+    let Some(mut cur_range) = ranges.pop() else { return };
 
     while let Some(r) = ranges.pop() {
         if r.end() >= cur_range.start() {
@@ -136,11 +168,11 @@ fn merge_ranges(ranges: &mut Vec<RangeInclusive<i32>>, scratch: &mut Vec<RangeIn
     ranges.append(scratch);
 }
 
-fn part2<const RANGE: i32>(sensors: &[Sensor]) -> i64 {
+fn tuning_frequency(sensors: &[Sensor], bound: i32) -> Result<i64> {
     let mut covered_ranges = Vec::<RangeInclusive<i32>>::new();
     let mut scratch = Vec::<RangeInclusive<i32>>::new();
 
-    for row in 0..=RANGE {
+    for row in 0..=bound {
         covered_ranges.clear();
         scratch.clear();
 
@@ -158,43 +190,9 @@ fn part2<const RANGE: i32>(sensors: &[Sensor]) -> i64 {
                 first.start() + 1
             };
 
-            return x as i64 * 4_000_000 + row as i64;
+            return Ok(x as i64 * 4_000_000 + row as i64);
         }
     }
 
-    panic!("hole not found");
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc_lib::Example;
-
-    #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let (sensors, beacons) = parse(&data).unwrap();
-        let expected = 26;
-        let actual = part1::<10>(&sensors, &beacons);
-
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn part2_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let (sensors, _) = parse(&data).unwrap();
-        let expected = 56_000_011;
-        let actual = part2::<20>(&sensors);
-
-        assert_eq!(expected, actual);
-    }
+    Err(eyre!("No gap found in the sensors' coverage"))
 }
@@ -0,0 +1,255 @@
+use std::collections::{HashMap, VecDeque};
+
+use aoc_lib::{Bench, BenchResult, Day, ParseResult, UserError};
+use color_eyre::{eyre::eyre, Report, Result};
+
+use crate::{
+    solution::{self, Runner, Solution},
+    DayError,
+};
+
+pub const DAY: Day = solution::day::<Day16>();
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const NAME: &'static str = "Proboscidea Volcanium";
+    const OTHER: &'static [(&'static str, Runner)] =
+        &[("Parse", run_parse), ("Compress", run_compress)];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<u32, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<u32, DayError> {
+        Ok(part2(data))
+    }
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    let input = solution::normalise::<Day16>(input).map_err(UserError)?;
+    b.bench(|| {
+        let data = parse_scan(&input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+fn run_compress(input: &str, b: Bench) -> BenchResult {
+    let input = solution::normalise::<Day16>(input).map_err(UserError)?;
+    let data = parse_scan(&input).map_err(UserError)?;
+    b.bench(|| {
+        let network = compress(&data).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(network))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ValveId(usize);
+
+#[derive(Debug, Clone)]
+struct Valve {
+    flow_rate: u32,
+    tunnels: Vec<ValveId>,
+}
+
+#[derive(Debug, Clone)]
+struct Scan {
+    start: ValveId,
+    valves: Vec<Valve>,
+}
+
+/// The scan reduced to only the valves worth opening, with the travel time between each of them.
+#[derive(Debug, Clone)]
+pub struct Network {
+    flow_rates: Vec<u32>,
+    /// Travel times between valves, including the start valve, which is always the last one.
+    distances: Vec<u8>,
+}
+
+impl Network {
+    fn start(&self) -> usize {
+        self.flow_rates.len()
+    }
+
+    fn distance(&self, from: usize, to: usize) -> u8 {
+        self.distances[from * (self.flow_rates.len() + 1) + to]
+    }
+}
+
+fn parse_scan(input: &str) -> Result<Scan> {
+    fn split_line(line: &str) -> Option<(&str, &str, &str)> {
+        let line = line.strip_prefix("Valve ")?;
+        let (name, line) = line.split_once(" has flow rate=")?;
+        let (flow_rate, line) = line.split_once("; ")?;
+        let tunnels = line
+            .strip_prefix("tunnels lead to valves ")
+            .or_else(|| line.strip_prefix("tunnel leads to valve "))?;
+
+        Some((name, flow_rate, tunnels))
+    }
+
+    let mut valves: Vec<Option<Valve>> = Vec::new();
+    let mut id_map = HashMap::new();
+
+    let mut get_id = |name, valves: &mut Vec<Option<Valve>>| {
+        *id_map.entry(name).or_insert_with(|| {
+            valves.push(None);
+            ValveId(valves.len() - 1)
+        })
+    };
+
+    for line in input.lines().map(str::trim) {
+        let Some((name, flow_rate, tunnels)) = split_line(line) else {
+            return Err(eyre!("Invalid line: `{}`", line));
+        };
+
+        let id = get_id(name, &mut valves);
+        let flow_rate = flow_rate.parse()?;
+        let tunnels = tunnels
+            .split(", ")
+            .map(|t| get_id(t, &mut valves))
+            .collect();
+
+        valves[id.0] = Some(Valve { flow_rate, tunnels });
+    }
+
+    let start = get_id("AA", &mut valves);
+    let valves = valves
+        .into_iter()
+        .map(|v| v.ok_or_else(|| eyre!("Tunnel leads to undefined valve")))
+        .collect::<Result<_>>()?;
+
+    Ok(Scan { start, valves })
+}
+
+fn compress(scan: &Scan) -> Result<Network> {
+    let useful: Vec<ValveId> = scan
+        .valves
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| v.flow_rate > 0)
+        .map(|(id, _)| ValveId(id))
+        .collect();
+
+    // The search tracks opened valves in a u64 bitmask.
+    if useful.len() >= 64 {
+        return Err(eyre!("Too many valves with a flow rate: {}", useful.len()));
+    }
+
+    let mut distances = Vec::with_capacity((useful.len() + 1) * (useful.len() + 1));
+    let mut steps = vec![u8::MAX; scan.valves.len()];
+    let mut queue = VecDeque::new();
+
+    for &from in useful.iter().chain([&scan.start]) {
+        steps.fill(u8::MAX);
+        steps[from.0] = 0;
+        queue.push_back(from);
+
+        while let Some(cur) = queue.pop_front() {
+            let next_steps = steps[cur.0].saturating_add(1);
+            for &tunnel in &scan.valves[cur.0].tunnels {
+                if steps[tunnel.0] == u8::MAX {
+                    steps[tunnel.0] = next_steps;
+                    queue.push_back(tunnel);
+                }
+            }
+        }
+
+        distances.extend(useful.iter().chain([&scan.start]).map(|to| steps[to.0]));
+    }
+
+    Ok(Network {
+        flow_rates: useful.iter().map(|v| scan.valves[v.0].flow_rate).collect(),
+        distances,
+    })
+}
+
+/// Walks every order of opening valves that fits in the time limit, calling `visit` with the
+/// set of opened valves and the total pressure they release.
+fn for_each_path(network: &Network, time: u8, mut visit: impl FnMut(u64, u32)) {
+    fn step(
+        network: &Network,
+        pos: usize,
+        time_left: u8,
+        opened: u64,
+        released: u32,
+        visit: &mut impl FnMut(u64, u32),
+    ) {
+        visit(opened, released);
+
+        for (next, &flow_rate) in network.flow_rates.iter().enumerate() {
+            if opened & (1 << next) != 0 {
+                continue;
+            }
+
+            // +1 for the minute it takes to open the valve.
+            let cost = network.distance(pos, next).saturating_add(1);
+            let Some(time_left) = time_left.checked_sub(cost).filter(|&t| t > 0) else {
+                continue;
+            };
+
+            step(
+                network,
+                next,
+                time_left,
+                opened | (1 << next),
+                released + flow_rate * time_left as u32,
+                visit,
+            );
+        }
+    }
+
+    step(network, network.start(), time, 0, 0, &mut visit);
+}
+
+pub type Parsed = Network;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    compress(&parse_scan(input)?)
+}
+
+pub fn part1(network: &Network) -> u32 {
+    let mut best = 0;
+    for_each_path(network, 30, |_, released| best = best.max(released));
+    best
+}
+
+pub fn part2(network: &Network) -> u32 {
+    let mut best_per_set = HashMap::new();
+    for_each_path(network, 26, |opened, released| {
+        let best = best_per_set.entry(opened).or_insert(0);
+        *best = released.max(*best);
+    });
+
+    let mut paths: Vec<(u64, u32)> = best_per_set.into_iter().collect();
+    paths.sort_unstable_by_key(|&(_, pressure)| std::cmp::Reverse(pressure));
+
+    // We and the elephant must open disjoint sets of valves. Because the paths are sorted by
+    // pressure, we can stop searching as soon as a pair can't beat the current best.
+    let mut best = 0;
+    for (idx, &(my_valves, my_pressure)) in paths.iter().enumerate() {
+        if my_pressure * 2 <= best {
+            break;
+        }
+
+        for &(elephant_valves, elephant_pressure) in &paths[idx + 1..] {
+            if my_pressure + elephant_pressure <= best {
+                break;
+            }
+            if my_valves & elephant_valves == 0 {
+                best = my_pressure + elephant_pressure;
+            }
+        }
+    }
+
+    best
+}
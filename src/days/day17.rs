@@ -1,63 +1,69 @@
-use std::collections::HashSet;
+use std::collections::HashMap;
 
-use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{eyre::eyre, Report, Result};
-use derive_more::Add;
+use aoc_lib::Day;
+use color_eyre::Result;
 
-// 16:43
-pub const DAY: Day = Day {
-    day: 17,
-    name: "Template",
-    part_1: run_part1,
-    part_2: None,
-    other: &[("Parse", run_parse)],
+use crate::{
+    geometry::{Point2, Vec2},
+    grid::Grid,
+    input::{ParseError, Shape},
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
 };
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1(&data)))
-}
+// 16:43
+pub const DAY: Day = solution::day::<Day17>();
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
-}
+pub struct Day17;
 
-#[derive(Debug, Clone, Copy, Add, PartialEq, Eq, Hash)]
-struct Point {
-    x: i16,
-    y: i16,
-}
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const NAME: &'static str = "Pyroclastic Flow";
+    const INPUT: Shape = Shape::Line;
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
 
-impl Point {
-    const DOWN: Self = Self::new(0, -1);
-    const LEFT: Self = Self::new(-1, 0);
-    const RIGHT: Self = Self::new(1, 0);
+    type Parsed<'a> = Parsed;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    const fn new(x: i16, y: i16) -> Self {
-        Self { x, y }
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<u64, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<u64, DayError> {
+        Ok(part2(data))
     }
 }
 
+// Unlike the maps, the tower grows upwards, so `y` increases going up.
+type Point = Point2<i32>;
+type Offset = Vec2<i32>;
+
+const DOWN: Offset = Vec2::new(0, -1);
+const LEFT: Offset = Vec2::new(-1, 0);
+const RIGHT: Offset = Vec2::new(1, 0);
+
 #[derive(Debug, Clone, Copy)]
-enum Wind {
+pub enum Wind {
     Left,
     Right,
 }
 
 impl Wind {
-    fn to_rel_point(self) -> Point {
+    fn to_offset(self) -> Offset {
         match self {
-            Wind::Left => Point::LEFT,
-            Wind::Right => Point::RIGHT,
+            Wind::Left => LEFT,
+            Wind::Right => RIGHT,
         }
     }
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Shape {
+enum RockShape {
     Flat,
     Cross,
     Angle,
@@ -65,77 +71,86 @@ enum Shape {
     Block,
 }
 
-impl Shape {
-    const COL_FLAT: &[Point] = &[
-        Point::new(0, 0),
-        Point::new(1, 0),
-        Point::new(2, 0),
-        Point::new(3, 0),
+impl RockShape {
+    const ORDER: [RockShape; 5] = [
+        RockShape::Flat,
+        RockShape::Cross,
+        RockShape::Angle,
+        RockShape::Vertical,
+        RockShape::Block,
     ];
-    const COL_CROSS: &[Point] = &[
-        Point::new(1, 2),
-        Point::new(0, 1),
-        Point::new(1, 1),
-        Point::new(2, 1),
-        Point::new(1, 0),
+
+    const COL_FLAT: &[Offset] = &[
+        Vec2::new(0, 0),
+        Vec2::new(1, 0),
+        Vec2::new(2, 0),
+        Vec2::new(3, 0),
     ];
-    const COL_ANGLE: &[Point] = &[
-        Point::new(2, 2),
-        Point::new(2, 1),
-        Point::new(0, 0),
-        Point::new(1, 0),
-        Point::new(2, 0),
+    const COL_CROSS: &[Offset] = &[
+        Vec2::new(1, 2),
+        Vec2::new(0, 1),
+        Vec2::new(1, 1),
+        Vec2::new(2, 1),
+        Vec2::new(1, 0),
     ];
-    const COL_VERTICAL: &[Point] = &[
-        Point::new(0, 3),
-        Point::new(0, 2),
-        Point::new(0, 1),
-        Point::new(0, 0),
+    const COL_ANGLE: &[Offset] = &[
+        Vec2::new(2, 2),
+        Vec2::new(2, 1),
+        Vec2::new(0, 0),
+        Vec2::new(1, 0),
+        Vec2::new(2, 0),
     ];
-    const COL_BLOCK: &[Point] = &[
-        Point::new(0, 1),
-        Point::new(1, 1),
-        Point::new(0, 0),
-        Point::new(1, 0),
+    const COL_VERTICAL: &[Offset] = &[
+        Vec2::new(0, 3),
+        Vec2::new(0, 2),
+        Vec2::new(0, 1),
+        Vec2::new(0, 0),
+    ];
+    const COL_BLOCK: &[Offset] = &[
+        Vec2::new(0, 1),
+        Vec2::new(1, 1),
+        Vec2::new(0, 0),
+        Vec2::new(1, 0),
     ];
 
-    fn next(self) -> Self {
-        use Shape::*;
-        match self {
-            Flat => Cross,
-            Cross => Angle,
-            Angle => Vertical,
-            Vertical => Block,
-            Block => Flat,
-        }
-    }
-
-    fn collision_coords(self) -> &'static [Point] {
+    fn collision_coords(self) -> &'static [Offset] {
         match self {
-            Shape::Flat => Self::COL_FLAT,
-            Shape::Cross => Self::COL_CROSS,
-            Shape::Angle => Self::COL_ANGLE,
-            Shape::Vertical => Self::COL_VERTICAL,
-            Shape::Block => Self::COL_BLOCK,
+            RockShape::Flat => Self::COL_FLAT,
+            RockShape::Cross => Self::COL_CROSS,
+            RockShape::Angle => Self::COL_ANGLE,
+            RockShape::Vertical => Self::COL_VERTICAL,
+            RockShape::Block => Self::COL_BLOCK,
         }
     }
 }
 
-fn parse(input: &str) -> Result<Vec<Wind>> {
-    input
-        .trim()
-        .bytes()
-        .map(|b| match b {
-            b'<' => Ok(Wind::Left),
-            b'>' => Ok(Wind::Right),
-            _ => Err(eyre!("invalid character")),
+pub type Parsed = Vec<Wind>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    let jets = input.trim();
+    let wind: Vec<_> = jets
+        .char_indices()
+        .map(|(idx, c)| match c {
+            '<' => Ok(Wind::Left),
+            '>' => Ok(Wind::Right),
+            _ => Err(ParseError::at(
+                input,
+                &jets[idx..idx + c.len_utf8()],
+                "`<` or `>`",
+            )),
         })
-        .collect()
+        .collect::<Result<_, _>>()?;
+
+    if wind.is_empty() {
+        return Err(ParseError::at(input, jets, "a jet pattern").into());
+    }
+
+    Ok(wind)
 }
 
-const LEFT_WALL: i16 = -1;
-const RIGHT_WALL: i16 = 7;
-const FLOOR: i16 = 0;
+const LEFT_WALL: i32 = -1;
+const RIGHT_WALL: i32 = 7;
+const FLOOR: i32 = 0;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum CollisionCheck {
@@ -143,82 +158,108 @@ enum CollisionCheck {
     Blocked,
 }
 
-fn collision_check(set_points: &HashSet<Point>, pos: Point) -> CollisionCheck {
-    if pos.x == LEFT_WALL || pos.x == RIGHT_WALL || pos.y == FLOOR || set_points.contains(&pos) {
-        CollisionCheck::Blocked
-    } else {
-        CollisionCheck::Clear
+/// The rows of the chamber above the floor, with the bottom row first.
+type Chamber = Grid<bool>;
+
+fn chamber_cell(pos: Point) -> (usize, usize) {
+    (pos.x as usize, (pos.y - FLOOR - 1) as usize)
+}
+
+fn collision_check(chamber: &Chamber, pos: Point) -> CollisionCheck {
+    if pos.x <= LEFT_WALL || pos.x >= RIGHT_WALL || pos.y <= FLOOR {
+        return CollisionCheck::Blocked;
+    }
+
+    let (x, y) = chamber_cell(pos);
+    match chamber.get(x, y) {
+        Some(true) => CollisionCheck::Blocked,
+        // Anything above the chamber hasn't been filled yet.
+        Some(false) | None => CollisionCheck::Clear,
     }
 }
 
-fn part1(wind: &[Wind]) -> i16 {
-    let mut cur_shape = Shape::Block;
-    let mut shapes = std::iter::from_fn(move || {
-        cur_shape = cur_shape.next();
-        Some(cur_shape)
-    });
-    let mut jets = wind.iter().copied().cycle();
+pub fn part1(wind: &[Wind]) -> u64 {
+    tower_height(wind, 2022)
+}
+
+pub fn part2(wind: &[Wind]) -> u64 {
+    tower_height(wind, 1_000_000_000_000)
+}
+
+fn tower_height(wind: &[Wind], num_rocks: u64) -> u64 {
+    let mut chamber = Chamber::new((RIGHT_WALL - LEFT_WALL - 1) as usize, 0, false);
+    let mut column_heights = [FLOOR; 7];
+    let mut max_y = FLOOR;
+    let mut jet_idx = 0;
 
-    let mut set_points = HashSet::new();
-    let mut spawn_height = 4;
-    let mut max_y = 0;
+    // The tower eventually settles into a repeating pattern. Once we see the same rock falling
+    // with the same jet onto the same surface, we can skip ahead by whole cycles.
+    let mut seen_states = HashMap::new();
+    let mut skipped_height = 0;
+    let mut cycle_found = false;
 
-    for _ in 0..2022 {
-        let mut cur_position = Point::new(2, spawn_height);
-        let shape = shapes.next().unwrap();
+    let mut rock = 0;
+    while rock < num_rocks {
+        let shape_idx = (rock % RockShape::ORDER.len() as u64) as usize;
+        let shape = RockShape::ORDER[shape_idx];
+        let mut cur_position = Point::new(2, max_y + 4);
 
-        for push_dir in jets.by_ref() {
-            let next_position = cur_position + push_dir.to_rel_point();
+        loop {
+            let push_dir = wind[jet_idx];
+            jet_idx = (jet_idx + 1) % wind.len();
+
+            let next_position = cur_position + push_dir.to_offset();
             if shape
                 .collision_coords()
                 .iter()
-                .map(|&p| p + next_position)
-                .all(|p| collision_check(&set_points, p) == CollisionCheck::Clear)
+                .map(|&p| next_position + p)
+                .all(|p| collision_check(&chamber, p) == CollisionCheck::Clear)
             {
                 cur_position = next_position;
             }
 
-            let next_position = cur_position + Point::DOWN;
+            let next_position = cur_position + DOWN;
 
             if shape
                 .collision_coords()
                 .iter()
-                .map(|&p| p + next_position)
-                .all(|p| collision_check(&set_points, p) == CollisionCheck::Clear)
+                .map(|&p| next_position + p)
+                .all(|p| collision_check(&chamber, p) == CollisionCheck::Clear)
             {
                 cur_position = next_position;
                 continue;
             }
 
             for &pos in shape.collision_coords() {
-                set_points.insert(pos + cur_position);
+                let pos = cur_position + pos;
+                let (x, y) = chamber_cell(pos);
+                while chamber.height() <= y {
+                    chamber.push_row(false);
+                }
+                chamber[(x, y)] = true;
+                column_heights[pos.x as usize] = column_heights[pos.x as usize].max(pos.y);
             }
             max_y = max_y.max(shape.collision_coords()[0].y + cur_position.y);
-            spawn_height = max_y + 4;
 
             break;
         }
-    }
-
-    max_y
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc_lib::Example;
+        rock += 1;
 
-    #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
-
-        let wind = parse(&data).unwrap();
-        let expected = 3068;
-        let actual = part1(&wind);
+        if !cycle_found {
+            let surface = column_heights.map(|h| max_y - h);
+            if let Some((prev_rock, prev_height)) =
+                seen_states.insert((shape_idx, jet_idx, surface), (rock, max_y))
+            {
+                let cycle_len = rock - prev_rock;
+                let cycle_height = (max_y - prev_height) as u64;
+                let cycles = (num_rocks - rock) / cycle_len;
 
-        assert_eq!(expected, actual);
+                rock += cycles * cycle_len;
+                skipped_height = cycles * cycle_height;
+                cycle_found = true;
+            }
+        }
     }
+
+    max_y as u64 + skipped_height
 }
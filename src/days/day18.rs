@@ -0,0 +1,178 @@
+use aoc_lib::Day;
+use color_eyre::{eyre::eyre, Result};
+use derive_more::{Add, Sub};
+
+use crate::{
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
+};
+
+pub const DAY: Day = solution::day::<Day18>();
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    const NAME: &'static str = "Boiling Boulders";
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part2(data))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Add, Sub)]
+pub struct Point {
+    x: i16,
+    y: i16,
+    z: i16,
+}
+
+impl Point {
+    const fn new(x: i16, y: i16, z: i16) -> Self {
+        Self { x, y, z }
+    }
+
+    fn neighbours(self) -> [Point; 6] {
+        [
+            self + Point::new(-1, 0, 0),
+            self + Point::new(1, 0, 0),
+            self + Point::new(0, -1, 0),
+            self + Point::new(0, 1, 0),
+            self + Point::new(0, 0, -1),
+            self + Point::new(0, 0, 1),
+        ]
+    }
+}
+
+/// A dense bounding box around the droplet, padded by one on each side so that the outside
+/// air is connected all the way around.
+struct Volume {
+    min: Point,
+    max: Point,
+    filled: Vec<bool>,
+}
+
+impl Volume {
+    fn new(cubes: &[Point]) -> Self {
+        let (min, max) = cubes
+            .iter()
+            .fold(None, |bounds: Option<(Point, Point)>, &p| {
+                let Some((min, max)) = bounds else {
+                    return Some((p, p));
+                };
+
+                let min = Point::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+                let max = Point::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+                Some((min, max))
+            })
+            .unwrap_or_default();
+
+        let mut volume = Volume {
+            min: min + Point::new(-1, -1, -1),
+            max: max + Point::new(1, 1, 1),
+            filled: Vec::new(),
+        };
+
+        let size = volume.max - volume.min + Point::new(1, 1, 1);
+        volume.filled = vec![false; size.x as usize * size.y as usize * size.z as usize];
+
+        for &cube in cubes {
+            if let Some(idx) = volume.to_idx(cube) {
+                volume.filled[idx] = true;
+            }
+        }
+
+        volume
+    }
+
+    fn to_idx(&self, p: Point) -> Option<usize> {
+        if p.x < self.min.x
+            || p.y < self.min.y
+            || p.z < self.min.z
+            || p.x > self.max.x
+            || p.y > self.max.y
+            || p.z > self.max.z
+        {
+            return None;
+        }
+
+        let width = (self.max.x - self.min.x + 1) as usize;
+        let height = (self.max.y - self.min.y + 1) as usize;
+        let x = (p.x - self.min.x) as usize;
+        let y = (p.y - self.min.y) as usize;
+        let z = (p.z - self.min.z) as usize;
+
+        Some((z * height + y) * width + x)
+    }
+
+    fn is_filled(&self, p: Point) -> bool {
+        matches!(self.to_idx(p), Some(idx) if self.filled[idx])
+    }
+}
+
+pub type Parsed = Vec<Point>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    input
+        .lines()
+        .map(str::trim)
+        .map(|line| {
+            let mut coords = line.split(',').map(str::parse);
+            let (Some(x), Some(y), Some(z), None) =
+                (coords.next(), coords.next(), coords.next(), coords.next())
+            else {
+                return Err(eyre!("Invalid cube: `{}`", line));
+            };
+
+            Ok(Point::new(x?, y?, z?))
+        })
+        .collect()
+}
+
+pub fn part1(cubes: &[Point]) -> usize {
+    let volume = Volume::new(cubes);
+
+    cubes
+        .iter()
+        .flat_map(|c| c.neighbours())
+        .filter(|&n| !volume.is_filled(n))
+        .count()
+}
+
+pub fn part2(cubes: &[Point]) -> usize {
+    let volume = Volume::new(cubes);
+    let mut visited = vec![false; volume.filled.len()];
+    let mut stack = vec![volume.min];
+    visited[volume.to_idx(volume.min).unwrap()] = true;
+
+    // Flood the air around the droplet, counting every time we bump into a cube face.
+    let mut faces = 0;
+    while let Some(cur) = stack.pop() {
+        for neighbour in cur.neighbours() {
+            let Some(idx) = volume.to_idx(neighbour) else {
+                continue;
+            };
+            if volume.filled[idx] {
+                faces += 1;
+            } else if !visited[idx] {
+                visited[idx] = true;
+                stack.push(neighbour);
+            }
+        }
+    }
+
+    faces
+}
@@ -0,0 +1,307 @@
+use aoc_lib::{Bench, BenchResult, Day, NoError, UserError};
+use color_eyre::{eyre::eyre, Result};
+use rayon::prelude::*;
+
+use crate::{
+    solution::{self, run_parse, Part, Runner, Solution, Variant},
+    DayError,
+};
+
+pub const DAY: Day = solution::day::<Day19>();
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    const NAME: &'static str = "Not Enough Minerals";
+    const OTHER: &'static [(&'static str, Runner)] = &[
+        ("Parse", run_parse::<Self>),
+        ("Part 1 Parallel", run_part1_parallel),
+        ("Part 2 Parallel", run_part2_parallel),
+    ];
+    const VARIANTS: &'static [Variant] = &[
+        Variant {
+            name: "Part 1 Parallel",
+            part: Part::One,
+            solve: |input| Ok(part1_parallel(&parse(input)?).to_string()),
+        },
+        Variant {
+            name: "Part 2 Parallel",
+            part: Part::Two,
+            solve: |input| Ok(part2_parallel(&parse(input)?).to_string()),
+        },
+    ];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = u16;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<u16, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<u32, DayError> {
+        Ok(part2(data))
+    }
+}
+
+fn run_part1_parallel(input: &str, b: Bench) -> BenchResult {
+    let input = solution::normalise::<Day19>(input).map_err(UserError)?;
+    let data = parse(&input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part1_parallel(&data)))
+}
+
+fn run_part2_parallel(input: &str, b: Bench) -> BenchResult {
+    let input = solution::normalise::<Day19>(input).map_err(UserError)?;
+    let data = parse(&input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part2_parallel(&data)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cost {
+    ore: u16,
+    clay: u16,
+    obsidian: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Blueprint {
+    id: u16,
+    ore_robot: Cost,
+    clay_robot: Cost,
+    obsidian_robot: Cost,
+    geode_robot: Cost,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    time_left: u16,
+    ore: u16,
+    clay: u16,
+    obsidian: u16,
+    ore_robots: u16,
+    clay_robots: u16,
+    obsidian_robots: u16,
+    /// Total geodes that will have been cracked when time runs out by the geode robots built
+    /// so far.
+    geodes: u16,
+}
+
+impl State {
+    fn new(time: u16) -> Self {
+        Self {
+            time_left: time,
+            ore: 0,
+            clay: 0,
+            obsidian: 0,
+            ore_robots: 1,
+            clay_robots: 0,
+            obsidian_robots: 0,
+            geodes: 0,
+        }
+    }
+
+    /// Waits until the robot can be afforded, then builds it. Returns `None` if the robot can't
+    /// be finished before time runs out.
+    fn build(self, cost: Cost) -> Option<Self> {
+        fn wait_time(have: u16, cost: u16, robots: u16) -> Option<u16> {
+            match (cost.saturating_sub(have), robots) {
+                (0, _) => Some(0),
+                (_, 0) => None,
+                (needed, robots) => Some(needed.div_ceil(robots)),
+            }
+        }
+
+        let ore_wait = wait_time(self.ore, cost.ore, self.ore_robots)?;
+        let clay_wait = wait_time(self.clay, cost.clay, self.clay_robots)?;
+        let obsidian_wait = wait_time(self.obsidian, cost.obsidian, self.obsidian_robots)?;
+        let wait = ore_wait.max(clay_wait).max(obsidian_wait) + 1; // Building takes a minute.
+
+        if wait >= self.time_left {
+            return None;
+        }
+
+        Some(Self {
+            time_left: self.time_left - wait,
+            ore: self.ore + self.ore_robots * wait - cost.ore,
+            clay: self.clay + self.clay_robots * wait - cost.clay,
+            obsidian: self.obsidian + self.obsidian_robots * wait - cost.obsidian,
+            ..self
+        })
+    }
+
+    /// The most geodes we could get if we could build a geode robot every remaining minute.
+    fn upper_bound(self) -> u16 {
+        self.geodes + self.time_left * self.time_left.saturating_sub(1) / 2
+    }
+}
+
+pub type Parsed = Vec<Blueprint>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    fn split_line(line: &str) -> Option<[&str; 7]> {
+        let line = line.strip_prefix("Blueprint ")?;
+        let (id, line) = line.split_once(": Each ore robot costs ")?;
+        let (ore_robot, line) = line.split_once(" ore. Each clay robot costs ")?;
+        let (clay_robot, line) = line.split_once(" ore. Each obsidian robot costs ")?;
+        let (obsidian_ore, line) = line.split_once(" ore and ")?;
+        let (obsidian_clay, line) = line.split_once(" clay. Each geode robot costs ")?;
+        let (geode_ore, line) = line.split_once(" ore and ")?;
+        let geode_obsidian = line.strip_suffix(" obsidian.")?;
+
+        Some([
+            id,
+            ore_robot,
+            clay_robot,
+            obsidian_ore,
+            obsidian_clay,
+            geode_ore,
+            geode_obsidian,
+        ])
+    }
+
+    let mut blueprints = Vec::new();
+
+    for line in input.lines().map(str::trim) {
+        let Some(parts) = split_line(line) else {
+            return Err(eyre!("Invalid blueprint: `{}`", line));
+        };
+
+        let [id, ore_robot, clay_robot, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] =
+            parts.map(str::parse::<u16>);
+
+        let ore = |ore| Cost {
+            ore,
+            clay: 0,
+            obsidian: 0,
+        };
+
+        blueprints.push(Blueprint {
+            id: id?,
+            ore_robot: ore(ore_robot?),
+            clay_robot: ore(clay_robot?),
+            obsidian_robot: Cost {
+                clay: obsidian_clay?,
+                ..ore(obsidian_ore?)
+            },
+            geode_robot: Cost {
+                obsidian: geode_obsidian?,
+                ..ore(geode_ore?)
+            },
+        });
+    }
+
+    Ok(blueprints)
+}
+
+fn max_geodes(blueprint: &Blueprint, time: u16) -> u16 {
+    // We can only spend so much of each resource per minute, so there's no point having more
+    // robots than that.
+    let max_ore_robots = blueprint
+        .ore_robot
+        .ore
+        .max(blueprint.clay_robot.ore)
+        .max(blueprint.obsidian_robot.ore)
+        .max(blueprint.geode_robot.ore);
+    let max_clay_robots = blueprint.obsidian_robot.clay;
+    let max_obsidian_robots = blueprint.geode_robot.obsidian;
+
+    let mut best = 0;
+    let mut stack = vec![State::new(time)];
+
+    while let Some(state) = stack.pop() {
+        best = best.max(state.geodes);
+        if state.upper_bound() <= best {
+            continue;
+        }
+
+        // Pushed in reverse priority order, so that building geode robots is tried first.
+        if state.ore_robots < max_ore_robots {
+            if let Some(next) = state.build(blueprint.ore_robot) {
+                stack.push(State {
+                    ore_robots: next.ore_robots + 1,
+                    ..next
+                });
+            }
+        }
+
+        if state.clay_robots < max_clay_robots {
+            if let Some(next) = state.build(blueprint.clay_robot) {
+                stack.push(State {
+                    clay_robots: next.clay_robots + 1,
+                    ..next
+                });
+            }
+        }
+
+        if state.obsidian_robots < max_obsidian_robots {
+            if let Some(next) = state.build(blueprint.obsidian_robot) {
+                stack.push(State {
+                    obsidian_robots: next.obsidian_robots + 1,
+                    ..next
+                });
+            }
+        }
+
+        if let Some(next) = state.build(blueprint.geode_robot) {
+            stack.push(State {
+                geodes: next.geodes + next.time_left,
+                ..next
+            });
+        }
+    }
+
+    best
+}
+
+pub fn part1(blueprints: &[Blueprint]) -> u16 {
+    blueprints.iter().map(|bp| bp.id * max_geodes(bp, 24)).sum()
+}
+
+pub fn part2(blueprints: &[Blueprint]) -> u32 {
+    blueprints
+        .iter()
+        .take(3)
+        .map(|bp| max_geodes(bp, 32) as u32)
+        .product()
+}
+
+fn part1_parallel(blueprints: &[Blueprint]) -> u16 {
+    blueprints
+        .par_iter()
+        .map(|bp| bp.id * max_geodes(bp, 24))
+        .sum()
+}
+
+fn part2_parallel(blueprints: &[Blueprint]) -> u32 {
+    blueprints[..blueprints.len().min(3)]
+        .par_iter()
+        .map(|bp| max_geodes(bp, 32) as u32)
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn part2_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let blueprints = parse(&data).unwrap();
+        let expected = [56, 62];
+
+        for (blueprint, expected) in blueprints.iter().zip(expected) {
+            let actual = max_geodes(blueprint, 32);
+            assert_eq!(expected, actual, "{}", blueprint.id);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use aoc_lib::{Bench, BenchResult, Day, NoError, UserError};
+use color_eyre::{eyre::eyre, Result};
+
+use crate::{
+    solution::{self, run_parse, Part, Runner, Solution, Variant},
+    DayError,
+};
+
+pub const DAY: Day = solution::day::<Day20>();
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    const NAME: &'static str = "Grove Positioning System";
+    const OTHER: &'static [(&'static str, Runner)] = &[
+        ("Parse", run_parse::<Self>),
+        ("Part 1 Blocked", run_part1_blocked),
+        ("Part 2 Blocked", run_part2_blocked),
+    ];
+    const VARIANTS: &'static [Variant] = &[
+        Variant {
+            name: "Part 1 Blocked",
+            part: Part::One,
+            solve: |input| Ok(grove_coordinates(&mix_blocked(&parse(input)?, 1, 1)).to_string()),
+        },
+        Variant {
+            name: "Part 2 Blocked",
+            part: Part::Two,
+            solve: |input| {
+                Ok(grove_coordinates(&mix_blocked(&parse(input)?, DECRYPTION_KEY, 10)).to_string())
+            },
+        },
+    ];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<i64, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<i64, DayError> {
+        Ok(part2(data))
+    }
+}
+
+const DECRYPTION_KEY: i64 = 811589153;
+
+fn run_part1_blocked(input: &str, b: Bench) -> BenchResult {
+    let input = solution::normalise::<Day20>(input).map_err(UserError)?;
+    let data = parse(&input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(grove_coordinates(&mix_blocked(&data, 1, 1))))
+}
+
+fn run_part2_blocked(input: &str, b: Bench) -> BenchResult {
+    let input = solution::normalise::<Day20>(input).map_err(UserError)?;
+    let data = parse(&input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(grove_coordinates(&mix_blocked(&data, DECRYPTION_KEY, 10))))
+}
+
+pub type Parsed = Vec<i64>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    let numbers: Vec<i64> = input
+        .lines()
+        .map(str::trim)
+        .map(str::parse)
+        .collect::<Result<_, _>>()?;
+
+    // The grove coordinates are relative to the zero, so we need exactly one.
+    match numbers.iter().filter(|&&n| n == 0).count() {
+        1 => Ok(numbers),
+        count => Err(eyre!("Expected exactly one zero, found {}", count)),
+    }
+}
+
+pub fn part1(numbers: &[i64]) -> i64 {
+    grove_coordinates(&mix_naive(numbers, 1, 1))
+}
+
+pub fn part2(numbers: &[i64]) -> i64 {
+    grove_coordinates(&mix_naive(numbers, DECRYPTION_KEY, 10))
+}
+
+/// Where an item ends up after moving, once it's been removed from a list of `len` items.
+fn new_position(pos: usize, value: i64, len: usize) -> usize {
+    // Moving a full lap around the remaining items brings us back to the same place.
+    let remaining = (len as i64 - 1).max(1);
+    (pos as i64 + value).rem_euclid(remaining) as usize
+}
+
+fn grove_coordinates(mixed: &[i64]) -> i64 {
+    let zero_idx = mixed
+        .iter()
+        .position(|&n| n == 0)
+        .expect("parse ensures there's a zero");
+
+    [1000, 2000, 3000]
+        .into_iter()
+        .map(|offset| mixed[(zero_idx + offset) % mixed.len()])
+        .sum()
+}
+
+fn mix_naive(numbers: &[i64], key: i64, rounds: usize) -> Vec<i64> {
+    // We need to track the original position of each number, because there are duplicates.
+    let mut list: Vec<(usize, i64)> = numbers.iter().map(|&n| n * key).enumerate().collect();
+
+    for _ in 0..rounds {
+        for id in 0..list.len() {
+            let pos = list.iter().position(|&(i, _)| i == id).unwrap();
+            let new_pos = new_position(pos, list[pos].1, list.len());
+
+            if new_pos > pos {
+                list[pos..=new_pos].rotate_left(1);
+            } else {
+                list[new_pos..=pos].rotate_right(1);
+            }
+        }
+    }
+
+    list.into_iter().map(|(_, n)| n).collect()
+}
+
+/// A list split into blocks of roughly `sqrt(n)` items, so that moving an item only shifts the
+/// items in two blocks instead of the whole list.
+struct BlockedList {
+    blocks: Vec<Vec<usize>>,
+    block_of: Vec<usize>,
+}
+
+impl BlockedList {
+    fn new(len: usize) -> Self {
+        let mut list = Self {
+            blocks: Vec::new(),
+            block_of: vec![0; len],
+        };
+        list.rebalance((0..len).collect());
+        list
+    }
+
+    fn rebalance(&mut self, order: Vec<usize>) {
+        let block_size = ((order.len() as f64).sqrt() as usize).max(1);
+
+        self.blocks.clear();
+        for (block_idx, chunk) in order.chunks(block_size).enumerate() {
+            chunk.iter().for_each(|&id| self.block_of[id] = block_idx);
+            self.blocks.push(chunk.to_vec());
+        }
+    }
+
+    fn to_order(&self) -> Vec<usize> {
+        self.blocks.iter().flatten().copied().collect()
+    }
+
+    fn remove(&mut self, id: usize) -> usize {
+        let block_idx = self.block_of[id];
+        let block = &mut self.blocks[block_idx];
+        let idx_in_block = block.iter().position(|&i| i == id).unwrap();
+        block.remove(idx_in_block);
+
+        let preceding: usize = self.blocks[..block_idx].iter().map(Vec::len).sum();
+        preceding + idx_in_block
+    }
+
+    fn insert(&mut self, id: usize, mut pos: usize) {
+        for (block_idx, block) in self.blocks.iter_mut().enumerate() {
+            if pos <= block.len() {
+                block.insert(pos, id);
+                self.block_of[id] = block_idx;
+                return;
+            }
+            pos -= block.len();
+        }
+
+        unreachable!("insert position past end of list");
+    }
+}
+
+fn mix_blocked(numbers: &[i64], key: i64, rounds: usize) -> Vec<i64> {
+    let values: Vec<i64> = numbers.iter().map(|&n| n * key).collect();
+    let mut list = BlockedList::new(values.len());
+
+    for _ in 0..rounds {
+        for (id, &value) in values.iter().enumerate() {
+            let pos = list.remove(id);
+            let new_pos = new_position(pos, value, values.len());
+            list.insert(id, new_pos);
+        }
+
+        // Moves can leave the blocks lopsided, so even them out between rounds.
+        list.rebalance(list.to_order());
+    }
+
+    list.to_order().into_iter().map(|id| values[id]).collect()
+}
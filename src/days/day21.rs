@@ -1,50 +1,152 @@
 use std::{
     collections::HashMap,
-    ops::{Add, Div, Index, IndexMut, Mul, Sub},
+    ops::{Index, IndexMut},
 };
 
-use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
-use color_eyre::{eyre::eyre, Report, Result};
+use aoc_lib::Day;
+use color_eyre::{eyre::eyre, Result};
 
-pub const DAY: Day = Day {
-    day: 21,
-    name: "Monkey Math",
-    part_1: run_part1,
-    part_2: None,
-    other: &[("Parse", run_parse)],
+use crate::{
+    input::{parse_at, ParseError},
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
 };
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1(data.clone())))
-}
+pub const DAY: Day = solution::day::<Day21>();
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    const NAME: &'static str = "Monkey Math";
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<i64, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<i64, DayError> {
+        part2(data)
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct MonkeyId(usize);
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy)]
+enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operator {
+    fn apply(self, left: i64, right: i64) -> i64 {
+        match self {
+            Operator::Add => left + right,
+            Operator::Sub => left - right,
+            Operator::Mul => left * right,
+            Operator::Div => left / right,
+        }
+    }
+
+    /// Finds `left` such that `left op right == result`, or an error if it isn't a whole number.
+    fn solve_left(self, result: i64, right: i64) -> Result<i64> {
+        match self {
+            Operator::Add => Ok(result - right),
+            Operator::Sub => Ok(result + right),
+            Operator::Mul => exact_div(result, right),
+            Operator::Div => Ok(result * right),
+        }
+    }
+
+    /// Finds `right` such that `left op right == result`, or an error if it isn't a whole number.
+    fn solve_right(self, left: i64, result: i64) -> Result<i64> {
+        match self {
+            Operator::Add => Ok(result - left),
+            Operator::Sub => Ok(left - result),
+            Operator::Mul => exact_div(result, left),
+            Operator::Div => exact_div(left, result),
+        }
+    }
+}
+
+fn exact_div(dividend: i64, divisor: i64) -> Result<i64> {
+    match dividend.checked_rem(divisor) {
+        Some(0) => Ok(dividend / divisor),
+        _ => Err(eyre!("No whole number solves {} / {}", dividend, divisor)),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
 enum Operation {
-    Literal(u64),
+    Literal(i64),
     Op {
         left: MonkeyId,
         right: MonkeyId,
-        op: fn(u64, u64) -> u64,
+        op: Operator,
     },
 }
 
 #[derive(Clone)]
-struct Monkeys {
+pub struct Monkeys {
     root: MonkeyId,
+    humn: Option<MonkeyId>,
     monkeys: Vec<Operation>,
 }
 
+impl Monkeys {
+    fn evaluate(&self, id: MonkeyId) -> i64 {
+        match self[id] {
+            Operation::Literal(value) => value,
+            Operation::Op { left, right, op } => {
+                op.apply(self.evaluate(left), self.evaluate(right))
+            }
+        }
+    }
+
+    /// Finds which monkeys depend on `target`, indexed by their ID. Each monkey is only visited
+    /// once, however many others share it.
+    fn dependents(&self, target: MonkeyId) -> Vec<bool> {
+        fn visit(
+            monkeys: &Monkeys,
+            id: MonkeyId,
+            target: MonkeyId,
+            memo: &mut [Option<bool>],
+        ) -> bool {
+            if let Some(depends) = memo[id.0] {
+                return depends;
+            }
+
+            let depends = id == target
+                || match monkeys[id] {
+                    Operation::Literal(_) => false,
+                    Operation::Op { left, right, .. } => {
+                        // Visit both sides, so the memo covers the whole tree.
+                        let left = visit(monkeys, left, target, memo);
+                        visit(monkeys, right, target, memo) || left
+                    }
+                };
+            memo[id.0] = Some(depends);
+            depends
+        }
+
+        let mut memo = vec![None; self.monkeys.len()];
+        (0..self.monkeys.len())
+            .map(|idx| visit(self, MonkeyId(idx), target, &mut memo))
+            .collect()
+    }
+}
+
 impl Index<MonkeyId> for Monkeys {
     type Output = Operation;
 
@@ -59,26 +161,31 @@ impl IndexMut<MonkeyId> for Monkeys {
     }
 }
 
-fn parse(input: &str) -> Result<Monkeys> {
+pub type Parsed = Monkeys;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
     let mut monkeys = Monkeys {
         root: MonkeyId(0),
+        humn: None,
         monkeys: Vec::new(),
     };
     let mut id_map = HashMap::new();
 
     for line in input.lines() {
         let Some((name, value)) = line.split_once(": ") else {
-            return Err(eyre!("Invalid monkey"));
+            return Err(ParseError::at(input, line, "a job like `root: pppw + sjmn`").into());
         };
 
         let id = *id_map.entry(name).or_insert_with(|| {
             let len = monkeys.monkeys.len();
-            monkeys.monkeys.push(Operation::Literal(u64::MAX));
+            monkeys.monkeys.push(Operation::Literal(i64::MAX));
             MonkeyId(len)
         });
 
         if name == "root" {
             monkeys.root = id;
+        } else if name == "humn" {
+            monkeys.humn = Some(id);
         }
 
         let op = if let Some((idx, op)) = value.match_indices(['+', '-', '*', '/']).next() {
@@ -87,26 +194,26 @@ fn parse(input: &str) -> Result<Monkeys> {
 
             let left = *id_map.entry(left.trim()).or_insert_with(|| {
                 let len = monkeys.monkeys.len();
-                monkeys.monkeys.push(Operation::Literal(u64::MAX));
+                monkeys.monkeys.push(Operation::Literal(i64::MAX));
                 MonkeyId(len)
             });
             let right = *id_map.entry(right.trim()).or_insert_with(|| {
                 let len = monkeys.monkeys.len();
-                monkeys.monkeys.push(Operation::Literal(u64::MAX));
+                monkeys.monkeys.push(Operation::Literal(i64::MAX));
                 MonkeyId(len)
             });
 
             let op = match op {
-                "+" => Add::add,
-                "-" => Sub::sub,
-                "*" => Mul::mul,
-                "/" => Div::div,
+                "+" => Operator::Add,
+                "-" => Operator::Sub,
+                "*" => Operator::Mul,
+                "/" => Operator::Div,
                 _ => unreachable!(),
             };
 
             Operation::Op { left, right, op }
         } else {
-            Operation::Literal(value.trim().parse()?)
+            Operation::Literal(parse_at(input, value.trim(), "a number or an operation")?)
         };
 
         monkeys[id] = op;
@@ -115,7 +222,8 @@ fn parse(input: &str) -> Result<Monkeys> {
     Ok(monkeys)
 }
 
-fn part1(mut monkeys: Monkeys) -> u64 {
+pub fn part1(monkeys: &Monkeys) -> i64 {
+    let mut monkeys = monkeys.clone();
     let mut src: Vec<usize> = (0..monkeys.monkeys.len()).collect();
     let mut dst = Vec::with_capacity(src.len());
     loop {
@@ -130,7 +238,7 @@ fn part1(mut monkeys: Monkeys) -> u64 {
                 continue
              };
 
-            monkeys.monkeys[idx] = Operation::Literal(op(left, right));
+            monkeys.monkeys[idx] = Operation::Literal(op.apply(left, right));
         }
 
         if let Operation::Literal(answer) = monkeys[monkeys.root] {
@@ -140,22 +248,61 @@ fn part1(mut monkeys: Monkeys) -> u64 {
     }
 }
 
+pub fn part2(monkeys: &Monkeys) -> Result<i64> {
+    let Some(humn) = monkeys.humn else {
+        return Err(eyre!("No humn monkey"));
+    };
+    let Operation::Op { left, right, .. } = monkeys[monkeys.root] else {
+        return Err(eyre!("Root monkey must be an operation"));
+    };
+
+    // Root's two sides must be equal, so whichever side doesn't depend on us gives us the value
+    // the other side must reach. We then walk down towards humn, undoing each operation.
+    let depends_on_humn = monkeys.dependents(humn);
+    let (mut unknown, mut target) = match (depends_on_humn[left.0], depends_on_humn[right.0]) {
+        (true, false) => (left, monkeys.evaluate(right)),
+        (false, true) => (right, monkeys.evaluate(left)),
+        (false, false) => return Err(eyre!("Neither side of root depends on humn")),
+        (true, true) => return Err(eyre!("Both sides of root depend on humn")),
+    };
+
+    while unknown != humn {
+        let Operation::Op { left, right, op } = monkeys[unknown] else {
+            return Err(eyre!("humn is not reachable from root"));
+        };
+
+        if depends_on_humn[left.0] {
+            target = op.solve_left(target, monkeys.evaluate(right))?;
+            unknown = left;
+        } else {
+            target = op.solve_right(monkeys.evaluate(left), target)?;
+            unknown = right;
+        }
+    }
+
+    Ok(target)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc_lib::Example;
 
     #[test]
-    fn part1_test() {
-        let data = aoc_lib::input(DAY.day)
-            .example(Example::Part1, 1)
-            .open()
-            .unwrap();
+    fn no_whole_answer_test() {
+        let input = "root: lhsd + rhsd\nlhsd: humn * twos\ntwos: 2\nrhsd: 7\nhumn: 1\n";
+        let monkeys = parse(input).unwrap();
+        assert!(part2(&monkeys).is_err());
 
-        let monkeys = parse(&data).unwrap();
-        let expected = 152;
-        let actual = part1(monkeys);
+        let input = input.replace("rhsd: 7", "rhsd: 8");
+        let monkeys = parse(&input).unwrap();
+        assert_eq!(4, part2(&monkeys).unwrap());
+    }
 
-        assert_eq!(expected, actual);
+    #[test]
+    fn humn_unused_test() {
+        let input = "root: lhsd + rhsd\nlhsd: 3\nrhsd: 7\nhumn: 1\n";
+        let monkeys = parse(input).unwrap();
+        let error = part2(&monkeys).unwrap_err();
+        assert_eq!("Neither side of root depends on humn", error.to_string());
     }
 }
@@ -0,0 +1,515 @@
+use std::collections::VecDeque;
+
+use aoc_lib::{Bench, BenchResult, Day, ParseResult, UserError};
+use color_eyre::{eyre::eyre, Report, Result};
+use derive_more::{Add, Neg, Sub};
+
+use crate::{
+    geometry::{Direction, Point2, Vec2},
+    input::ParseError,
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
+};
+
+pub const DAY: Day = solution::day::<Day22>();
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    const NAME: &'static str = "Monkey Map";
+    const OTHER: &'static [(&'static str, Runner)] =
+        &[("Parse", run_parse::<Self>), ("Fold Cube", run_fold)];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<usize, DayError> {
+        part2(data)
+    }
+}
+
+fn run_fold(input: &str, b: Bench) -> BenchResult {
+    let input = solution::normalise::<Day22>(input).map_err(UserError)?;
+    let data = parse(&input).map_err(UserError)?;
+    b.bench(|| {
+        let cube = Cube::fold(&data.board).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(cube))
+    })
+}
+
+type Point = Point2<i32>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Facing {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Facing {
+    fn turn_left(self) -> Self {
+        match self {
+            Facing::Right => Facing::Up,
+            Facing::Down => Facing::Right,
+            Facing::Left => Facing::Down,
+            Facing::Up => Facing::Left,
+        }
+    }
+
+    fn turn_right(self) -> Self {
+        match self {
+            Facing::Right => Facing::Down,
+            Facing::Down => Facing::Left,
+            Facing::Left => Facing::Up,
+            Facing::Up => Facing::Right,
+        }
+    }
+
+    fn reverse(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    fn offset(self) -> Vec2<i32> {
+        let dir = match self {
+            Facing::Right => Direction::Right,
+            Facing::Down => Direction::Down,
+            Facing::Left => Direction::Left,
+            Facing::Up => Direction::Up,
+        };
+        dir.offset()
+    }
+
+    fn score(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Void,
+    Open,
+    Wall,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Forward(u16),
+    TurnLeft,
+    TurnRight,
+}
+
+#[derive(Debug, Clone)]
+struct Board {
+    tiles: Vec<Tile>,
+    width: usize,
+    height: usize,
+}
+
+impl Board {
+    fn get(&self, pos: Point) -> Tile {
+        if pos.x < 0 || pos.y < 0 || pos.x as usize >= self.width || pos.y as usize >= self.height {
+            return Tile::Void;
+        }
+
+        self.tiles[pos.y as usize * self.width + pos.x as usize]
+    }
+
+    fn start(&self) -> Point {
+        let x = self.tiles[..self.width]
+            .iter()
+            .position(|&t| t == Tile::Open)
+            .unwrap_or(0);
+        Point::new(x as i32, 0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notes {
+    board: Board,
+    path: Vec<Instruction>,
+}
+
+pub type Parsed = Notes;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    let Some((board_str, path_str)) = input.split_once("\n\n") else {
+        return Err(eyre!("Invalid input"));
+    };
+
+    let rows: Vec<&str> = board_str.lines().map(str::trim_end).collect();
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let height = rows.len();
+    if width == 0 || height == 0 {
+        return Err(ParseError::at(input, board_str, "a map of the board").into());
+    }
+    let mut tiles = vec![Tile::Void; width * height];
+
+    for (row, tile_row) in rows.iter().zip(tiles.chunks_exact_mut(width)) {
+        for (byte, tile) in row.bytes().zip(tile_row) {
+            *tile = match byte {
+                b' ' => Tile::Void,
+                b'.' => Tile::Open,
+                b'#' => Tile::Wall,
+                _ => return Err(eyre!("Invalid character: `{}`", byte as char)),
+            };
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut path_str = path_str.trim();
+    while !path_str.is_empty() {
+        let digits = path_str.bytes().take_while(u8::is_ascii_digit).count();
+        let instr = match path_str.as_bytes()[0] {
+            b'L' => Instruction::TurnLeft,
+            b'R' => Instruction::TurnRight,
+            b'0'..=b'9' => Instruction::Forward(path_str[..digits].parse()?),
+            b => return Err(eyre!("Invalid path character: `{}`", b as char)),
+        };
+
+        path.push(instr);
+        path_str = &path_str[digits.max(1)..];
+    }
+
+    Ok(Notes {
+        board: Board {
+            tiles,
+            width,
+            height,
+        },
+        path,
+    })
+}
+
+/// Follows the path, using `wrap` to find where we end up after stepping off the edge of the
+/// map. Returns the final password.
+fn walk(notes: &Notes, wrap: impl Fn(Point, Facing) -> (Point, Facing)) -> usize {
+    let mut pos = notes.board.start();
+    let mut facing = Facing::Right;
+
+    for &instr in &notes.path {
+        match instr {
+            Instruction::TurnLeft => facing = facing.turn_left(),
+            Instruction::TurnRight => facing = facing.turn_right(),
+            Instruction::Forward(steps) => {
+                for _ in 0..steps {
+                    let mut next = (pos + facing.offset(), facing);
+                    if notes.board.get(next.0) == Tile::Void {
+                        next = wrap(pos, facing);
+                    }
+
+                    if notes.board.get(next.0) == Tile::Wall {
+                        break;
+                    }
+                    (pos, facing) = next;
+                }
+            }
+        }
+    }
+
+    1000 * (pos.y as usize + 1) + 4 * (pos.x as usize + 1) + facing.score()
+}
+
+pub fn part1(notes: &Notes) -> usize {
+    walk(notes, |pos, facing| {
+        // Walk backwards until we find the opposite edge.
+        let back = facing.reverse().offset();
+        let mut wrapped = pos;
+        while notes.board.get(wrapped + back) != Tile::Void {
+            wrapped += back;
+        }
+        (wrapped, facing)
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Add, Sub, Neg)]
+struct Vec3 {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+impl Vec3 {
+    const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    fn scale(self, s: i32) -> Self {
+        Self::new(self.x * s, self.y * s, self.z * s)
+    }
+
+    fn dot(self, other: Self) -> i32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+/// A face of the cube, with the directions its local right, down, and outward axes point in
+/// once the net has been folded.
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    origin: Point,
+    normal: Vec3,
+    right: Vec3,
+    down: Vec3,
+}
+
+impl Face {
+    fn facing_to_vec(&self, facing: Facing) -> Vec3 {
+        match facing {
+            Facing::Right => self.right,
+            Facing::Down => self.down,
+            Facing::Left => -self.right,
+            Facing::Up => -self.down,
+        }
+    }
+
+    fn vec_to_facing(&self, dir: Vec3) -> Facing {
+        if dir == self.right {
+            Facing::Right
+        } else if dir == self.down {
+            Facing::Down
+        } else if dir == -self.right {
+            Facing::Left
+        } else {
+            Facing::Up
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Cube {
+    size: i32,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    /// Folds the board's net into a cube, working out the orientation of each face by walking
+    /// across the net from the first face.
+    fn fold(board: &Board) -> Result<Cube> {
+        let area = board.tiles.iter().filter(|&&t| t != Tile::Void).count();
+        let size = ((area / 6) as f64).sqrt() as usize;
+        if size == 0
+            || size * size * 6 != area
+            || !board.width.is_multiple_of(size)
+            || !board.height.is_multiple_of(size)
+        {
+            return Err(eyre!("Board is not the net of a cube"));
+        }
+
+        let is_face =
+            |x: i32, y: i32| board.get(Point::new(x * size as i32, y * size as i32)) != Tile::Void;
+
+        let net_width = (board.width / size) as i32;
+        let net_height = (board.height / size) as i32;
+        let Some(first) = (0..net_height)
+            .flat_map(|y| (0..net_width).map(move |x| (x, y)))
+            .find(|&(x, y)| is_face(x, y))
+        else {
+            return Err(eyre!("Board is empty"));
+        };
+
+        let mut faces: Vec<Face> = Vec::new();
+        let mut visited = vec![false; (net_width * net_height) as usize];
+        let mut queue = VecDeque::new();
+        queue.push_back((
+            first,
+            Face {
+                origin: Point::new(0, 0),
+                normal: Vec3::new(0, 0, 1),
+                right: Vec3::new(1, 0, 0),
+                down: Vec3::new(0, 1, 0),
+            },
+        ));
+        visited[(first.1 * net_width + first.0) as usize] = true;
+
+        while let Some(((x, y), face)) = queue.pop_front() {
+            let face = Face {
+                origin: Point::new(x * size as i32, y * size as i32),
+                ..face
+            };
+            faces.push(face);
+
+            let Face {
+                normal,
+                right,
+                down,
+                ..
+            } = face;
+
+            // Each neighbour in the net folds away from us around our shared edge.
+            let neighbours = [
+                (
+                    (x + 1, y),
+                    Face {
+                        normal: right,
+                        right: -normal,
+                        ..face
+                    },
+                ),
+                (
+                    (x - 1, y),
+                    Face {
+                        normal: -right,
+                        right: normal,
+                        ..face
+                    },
+                ),
+                (
+                    (x, y + 1),
+                    Face {
+                        normal: down,
+                        down: -normal,
+                        ..face
+                    },
+                ),
+                (
+                    (x, y - 1),
+                    Face {
+                        normal: -down,
+                        down: normal,
+                        ..face
+                    },
+                ),
+            ];
+
+            for ((nx, ny), neighbour) in neighbours {
+                if !(0..net_width).contains(&nx) || !(0..net_height).contains(&ny) {
+                    continue;
+                }
+                let idx = (ny * net_width + nx) as usize;
+                if visited[idx] || !is_face(nx, ny) {
+                    continue;
+                }
+
+                visited[idx] = true;
+                queue.push_back(((nx, ny), neighbour));
+            }
+        }
+
+        let all_sides = [
+            Vec3::new(1, 0, 0),
+            Vec3::new(-1, 0, 0),
+            Vec3::new(0, 1, 0),
+            Vec3::new(0, -1, 0),
+            Vec3::new(0, 0, 1),
+            Vec3::new(0, 0, -1),
+        ];
+        if faces.len() != 6
+            || !all_sides
+                .iter()
+                .all(|&s| faces.iter().any(|f| f.normal == s))
+        {
+            return Err(eyre!("Board is not the net of a cube"));
+        }
+
+        Ok(Cube {
+            size: size as i32,
+            faces,
+        })
+    }
+
+    fn face_containing(&self, pos: Point) -> &Face {
+        self.faces
+            .iter()
+            .find(|f| {
+                (f.origin.x..f.origin.x + self.size).contains(&pos.x)
+                    && (f.origin.y..f.origin.y + self.size).contains(&pos.y)
+            })
+            .unwrap()
+    }
+
+    fn wrap(&self, pos: Point, facing: Facing) -> (Point, Facing) {
+        let face = self.face_containing(pos);
+        let dir = face.facing_to_vec(facing);
+
+        // Work in doubled coordinates, so that the center of each tile is on an integer. The cube
+        // spans -size..=size on each axis.
+        let n = self.size;
+        let local_x = pos.x - face.origin.x;
+        let local_y = pos.y - face.origin.y;
+        let pos_3d = face.normal.scale(n)
+            + face.right.scale(2 * local_x + 1 - n)
+            + face.down.scale(2 * local_y + 1 - n);
+
+        // Stepping over the edge puts us on the face the direction was pointing at, now heading
+        // away from the face we left.
+        let new_pos_3d = pos_3d + dir - face.normal;
+        let new_dir = -face.normal;
+        let new_face = self.faces.iter().find(|f| f.normal == dir).unwrap();
+
+        let new_x = (new_pos_3d.dot(new_face.right) + n - 1) / 2;
+        let new_y = (new_pos_3d.dot(new_face.down) + n - 1) / 2;
+
+        (
+            new_face.origin + Vec2::new(new_x, new_y),
+            new_face.vec_to_facing(new_dir),
+        )
+    }
+}
+
+fn walk_cube(notes: &Notes, cube: &Cube) -> usize {
+    walk(notes, |pos, facing| cube.wrap(pos, facing))
+}
+
+pub fn part2(notes: &Notes) -> Result<usize> {
+    let cube = Cube::fold(&notes.board)?;
+    Ok(walk_cube(notes, &cube))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn empty_map_test() {
+        for input in ["\n\n10R5\n", "   \n\n10R5\n"] {
+            let error = parse(input).unwrap_err();
+            assert!(error.downcast_ref::<ParseError>().is_some(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn cube_wrap_round_trip_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let notes = parse(&data).unwrap();
+        let cube = Cube::fold(&notes.board).unwrap();
+
+        // Stepping off any edge then turning around and stepping back should return us to
+        // where we started.
+        for y in 0..notes.board.height as i32 {
+            for x in 0..notes.board.width as i32 {
+                let pos = Point::new(x, y);
+                if notes.board.get(pos) == Tile::Void {
+                    continue;
+                }
+
+                for facing in [Facing::Right, Facing::Down, Facing::Left, Facing::Up] {
+                    if notes.board.get(pos + facing.offset()) != Tile::Void {
+                        continue;
+                    }
+
+                    let (wrapped, wrapped_facing) = cube.wrap(pos, facing);
+                    assert_ne!(notes.board.get(wrapped), Tile::Void);
+
+                    let (back, back_facing) = cube.wrap(wrapped, wrapped_facing.reverse());
+                    assert_eq!((pos, facing), (back, back_facing.reverse()));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_lib::Day;
+use color_eyre::{eyre::eyre, Result};
+
+use crate::{
+    geometry::{BoundingBox, Direction, Point2, Vec2},
+    input::Shape,
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
+};
+
+pub const DAY: Day = solution::day::<Day23>();
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    const NAME: &'static str = "Unstable Diffusion";
+    const INPUT: Shape = Shape::Grid;
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Parsed) -> Result<usize, DayError> {
+        Ok(part2(data))
+    }
+}
+
+type Point = Point2<i32>;
+type Offset = Vec2<i32>;
+
+const NEIGHBOURS: [Offset; 8] = [
+    Vec2::new(-1, -1),
+    Vec2::new(0, -1),
+    Vec2::new(1, -1),
+    Vec2::new(-1, 0),
+    Vec2::new(1, 0),
+    Vec2::new(-1, 1),
+    Vec2::new(0, 1),
+    Vec2::new(1, 1),
+];
+
+/// The order the elves consider moving in, starting from north, before it rotates each round.
+const ORDER: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+/// The three positions that must be empty for an elf to propose moving in `dir`: the step itself,
+/// and the diagonals either side of it.
+fn checks(dir: Direction) -> [Offset; 3] {
+    let step = dir.offset();
+    let side = Vec2::new(step.y, step.x);
+    [step + side, step, step - side]
+}
+
+/// The elves are stored sparsely, as the grove grows without bound as they spread out.
+#[derive(Debug, Clone)]
+pub struct Grove {
+    elves: HashSet<Point>,
+    first_direction: usize,
+}
+
+impl Grove {
+    /// Runs a single round, returning whether any elf moved.
+    fn step(&mut self, proposals: &mut HashMap<Point, (Point, u8)>) -> bool {
+        proposals.clear();
+
+        for &elf in &self.elves {
+            if NEIGHBOURS.iter().all(|&n| !self.elves.contains(&(elf + n))) {
+                continue;
+            }
+
+            let proposal = (0..4)
+                .map(|i| ORDER[(self.first_direction + i) % 4])
+                .find(|&dir| {
+                    checks(dir)
+                        .iter()
+                        .all(|&c| !self.elves.contains(&(elf + c)))
+                });
+
+            if let Some(dir) = proposal {
+                let target = elf + dir.offset();
+                proposals.entry(target).or_insert((elf, 0)).1 += 1;
+            }
+        }
+
+        let mut moved = false;
+        for (&target, &(elf, count)) in proposals.iter() {
+            if count == 1 {
+                self.elves.remove(&elf);
+                self.elves.insert(target);
+                moved = true;
+            }
+        }
+
+        self.first_direction = (self.first_direction + 1) % 4;
+        moved
+    }
+
+    fn empty_tiles(&self) -> usize {
+        // There's always at least one elf, as parsing checks for it.
+        let bounds = BoundingBox::from_points(self.elves.iter().copied()).unwrap();
+        let area = bounds.width() as usize * bounds.height() as usize;
+        area - self.elves.len()
+    }
+}
+
+pub type Parsed = Grove;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    let mut elves = HashSet::new();
+
+    for (y, line) in input.lines().enumerate() {
+        for (x, byte) in line.trim_end().bytes().enumerate() {
+            match byte {
+                b'#' => {
+                    elves.insert(Point::new(x as i32, y as i32));
+                }
+                b'.' => {}
+                _ => return Err(eyre!("Invalid character: `{}`", byte as char)),
+            }
+        }
+    }
+
+    if elves.is_empty() {
+        return Err(eyre!("No elves found"));
+    }
+
+    Ok(Grove {
+        elves,
+        first_direction: 0,
+    })
+}
+
+pub fn part1(grove: &Grove) -> usize {
+    let mut grove = grove.clone();
+    let mut proposals = HashMap::new();
+
+    for _ in 0..10 {
+        grove.step(&mut proposals);
+    }
+
+    grove.empty_tiles()
+}
+
+pub fn part2(grove: &Grove) -> usize {
+    let mut grove = grove.clone();
+    let mut proposals = HashMap::new();
+
+    let mut round = 1;
+    while grove.step(&mut proposals) {
+        round += 1;
+    }
+
+    round
+}
@@ -0,0 +1,203 @@
+use aoc_lib::Day;
+use color_eyre::{eyre::eyre, Result};
+
+use crate::{
+    geometry::Point2,
+    input::Shape,
+    search::{self, DenseVisited},
+    solution::{self, run_parse, Runner, Solution},
+    DayError,
+};
+
+pub const DAY: Day = solution::day::<Day24>();
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    const NAME: &'static str = "Blizzard Basin";
+    const INPUT: Shape = Shape::Grid;
+    const OTHER: &'static [(&'static str, Runner)] = &[("Parse", run_parse::<Self>)];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<usize, DayError> {
+        part1(data)
+    }
+
+    fn part2(data: &Parsed) -> Result<usize, DayError> {
+        part2(data)
+    }
+}
+
+type Point = Point2<i16>;
+
+/// Waiting where we are, or stepping to one of the neighbours.
+fn moves(pos: Point) -> [Point; 5] {
+    let [up, left, right, down] = pos.neighbours4();
+    [pos, up, left, right, down]
+}
+
+/// The valley interior, not including the walls. The start and goal sit just outside it.
+///
+/// Each blizzard only moves along its row or column, so the blizzards in each row and column
+/// are stored as a bitmask which is rotated by the time to find where they are.
+#[derive(Debug, Clone)]
+pub struct Valley {
+    width: usize,
+    height: usize,
+    start: Point,
+    goal: Point,
+    left: Vec<u128>,
+    right: Vec<u128>,
+    up: Vec<u128>,
+    down: Vec<u128>,
+}
+
+impl Valley {
+    fn period(&self) -> usize {
+        fn gcd(a: usize, b: usize) -> usize {
+            if b == 0 {
+                a
+            } else {
+                gcd(b, a % b)
+            }
+        }
+
+        self.width / gcd(self.width, self.height) * self.height
+    }
+
+    /// Gives every square we can stand on a different index, below `width * height + 2`.
+    fn to_idx(&self, p: Point) -> usize {
+        if p == self.start {
+            self.width * self.height
+        } else if p == self.goal {
+            self.width * self.height + 1
+        } else {
+            p.y as usize * self.width + p.x as usize
+        }
+    }
+
+    fn is_clear(&self, p: Point, time: usize) -> bool {
+        if p == self.start || p == self.goal {
+            return true;
+        }
+        if p.x < 0 || p.y < 0 || p.x as usize >= self.width || p.y as usize >= self.height {
+            return false;
+        }
+
+        let (x, y) = (p.x as usize, p.y as usize);
+        let (width, height) = (self.width, self.height);
+        let bit = |mask: u128, idx: usize| mask & (1 << idx) != 0;
+
+        // Look back along the row and column to where a blizzard would have had to start to be
+        // here now.
+        !(bit(self.right[y], (x + width - time % width) % width)
+            || bit(self.left[y], (x + time) % width)
+            || bit(self.down[x], (y + height - time % height) % height)
+            || bit(self.up[x], (y + time) % height))
+    }
+}
+
+pub type Parsed = Valley;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    let mut rows: Vec<Vec<u8>> = vec![Vec::new()];
+
+    for byte in input.trim().bytes() {
+        match byte {
+            b'#' | b'.' | b'<' | b'>' | b'^' | b'v' => rows.last_mut().unwrap().push(byte),
+            b'\r' => continue,
+            b'\n' => rows.push(Vec::new()),
+            _ => return Err(eyre!("Invalid character: `{}`", byte as char)),
+        }
+    }
+
+    let full_width = rows[0].len();
+    if rows.len() < 3 || full_width < 3 || rows.iter().any(|r| r.len() != full_width) {
+        return Err(eyre!("Invalid valley shape"));
+    }
+
+    let width = full_width - 2;
+    let height = rows.len() - 2;
+    if width > 128 || height > 128 {
+        return Err(eyre!("Valley too large: {}x{}", width, height));
+    }
+
+    let find_gap = |row: &[u8]| {
+        row.iter()
+            .position(|&b| b == b'.')
+            .map(|x| x as i16 - 1)
+            .ok_or_else(|| eyre!("No gap in valley wall"))
+    };
+    let start = Point::new(find_gap(&rows[0])?, -1);
+    let goal = Point::new(find_gap(&rows[height + 1])?, height as i16);
+
+    let mut valley = Valley {
+        width,
+        height,
+        start,
+        goal,
+        left: vec![0; height],
+        right: vec![0; height],
+        up: vec![0; width],
+        down: vec![0; width],
+    };
+
+    for (y, row) in rows[1..=height].iter().enumerate() {
+        for (x, &byte) in row[1..=width].iter().enumerate() {
+            match byte {
+                b'<' => valley.left[y] |= 1 << x,
+                b'>' => valley.right[y] |= 1 << x,
+                b'^' => valley.up[x] |= 1 << y,
+                b'v' => valley.down[x] |= 1 << y,
+                b'.' => {}
+                _ => return Err(eyre!("Wall inside valley at {},{}", x + 1, y + 1)),
+            }
+        }
+    }
+
+    Ok(valley)
+}
+
+/// Finds the earliest time we can reach `to`, leaving `from` at `start_time`.
+fn earliest_arrival(valley: &Valley, from: Point, to: Point, start_time: usize) -> Result<usize> {
+    // The blizzards repeat, so two visits to the same place at the same point in the cycle
+    // are the same state.
+    let period = valley.period();
+    let mut visited = DenseVisited::new(
+        (valley.width * valley.height + 2) * period,
+        |&(pos, time): &(Point, usize)| valley.to_idx(pos) * period + time,
+    );
+    let found = search::bfs_with(
+        &mut visited,
+        [(from, start_time % period)],
+        |&(pos, _), elapsed| {
+            let next_time = start_time + elapsed + 1;
+            moves(pos)
+                .into_iter()
+                .filter(move |&next| valley.is_clear(next, next_time))
+                .map(move |next| (next, next_time % period))
+        },
+        |&(pos, _)| pos == to,
+    );
+
+    let (_, cost) = found.ok_or_else(|| eyre!("No path found"))?;
+    Ok(start_time + cost)
+}
+
+pub fn part1(valley: &Valley) -> Result<usize> {
+    earliest_arrival(valley, valley.start, valley.goal, 0)
+}
+
+pub fn part2(valley: &Valley) -> Result<usize> {
+    let there = earliest_arrival(valley, valley.start, valley.goal, 0)?;
+    let back = earliest_arrival(valley, valley.goal, valley.start, there)?;
+    earliest_arrival(valley, valley.start, valley.goal, back)
+}
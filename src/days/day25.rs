@@ -1,82 +1,285 @@
-use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use std::{
+    fmt::{Display, Write},
+    io::BufRead,
+    iter::Sum,
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use aoc_lib::{Bench, BenchResult, Day, NoError, UserError};
 use color_eyre::{eyre::eyre, Report, Result};
 
-pub const DAY: Day = Day {
-    day: 25,
-    name: "Full of Hot Air",
-    part_1: run_part1,
-    part_2: None,
-    other: &[("Parse", run_parse)],
+use crate::{
+    input::{self, parse_at},
+    solution::{self, run_parse, Part, Runner, Solution, Variant},
+    DayError,
 };
 
-fn run_part1(input: &str, b: Bench) -> BenchResult {
-    let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1(&data)))
+pub const DAY: Day = solution::day::<Day25>();
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const NAME: &'static str = "Full of Hot Air";
+    const HAS_PART_2: bool = false;
+    const OTHER: &'static [(&'static str, Runner)] = &[
+        ("Parse", run_parse::<Self>),
+        ("Part 1 Digit-wise", run_part1_digitwise),
+    ];
+    const VARIANTS: &'static [Variant] = &[Variant {
+        name: "Part 1 Digit-wise",
+        part: Part::One,
+        solve: |input| Ok(part1_digitwise(&parse(input)?)),
+    }];
+
+    type Parsed<'a> = Parsed;
+    type Answer1 = String;
+    type Answer2 = NoError;
+
+    fn parse(input: &str) -> Result<Parsed, DayError> {
+        parse(input)
+    }
+
+    fn part1(data: &Parsed) -> Result<String, DayError> {
+        Ok(part1(data))
+    }
+
+    fn part2(_: &Parsed) -> Result<NoError, DayError> {
+        Err(eyre!("There is no part 2"))
+    }
 }
 
-fn run_parse(input: &str, b: Bench) -> BenchResult {
-    b.bench(|| {
-        let data = parse(input).map_err(UserError)?;
-        Ok::<_, Report>(ParseResult(data))
-    })
+fn run_part1_digitwise(input: &str, b: Bench) -> BenchResult {
+    let input = solution::normalise::<Day25>(input).map_err(UserError)?;
+    let data = parse(&input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part1_digitwise(&data)))
 }
 
-fn from_snafu(num_str: &str) -> Result<i64> {
-    let digits = num_str.trim().bytes().map(|b| match b {
-        b'0'..=b'2' => Ok((b - b'0') as i64),
-        b'-' => Ok(-1),
-        b'=' => Ok(-2),
-        _ => Err(eyre!("Invalid character")),
-    });
+/// A number in balanced base 5, with digits from -2 to 2.
+///
+/// The digits are stored least significant first, with no leading zeros, so zero has no digits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snafu {
+    digits: Vec<i8>,
+}
 
-    (0..)
-        .map(|b| i64::pow(5, b as u32))
-        .zip(digits.rev())
-        .map(|(mul, digit)| digit.map(|d| d * mul))
-        .sum()
+impl Snafu {
+    /// Builds a number from digits that may be outside the -2 to 2 range, carrying the excess
+    /// into the next digit.
+    fn from_unnormalised(raw: impl IntoIterator<Item = i64>) -> Self {
+        let mut digits = Vec::new();
+        let mut carry = 0;
+
+        let mut raw = raw.into_iter();
+        loop {
+            let value = match raw.next() {
+                Some(v) => v + carry,
+                None if carry != 0 => carry,
+                None => break,
+            };
+
+            carry = (value + 2).div_euclid(5);
+            digits.push((value - carry * 5) as i8);
+        }
+
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Snafu { digits }
+    }
+
+    fn to_i64(&self) -> i64 {
+        self.digits
+            .iter()
+            .rev()
+            .fold(0, |acc, &digit| acc * 5 + digit as i64)
+    }
 }
 
-fn to_snafu(mut number: i64, buf: &mut [u8; 64]) -> &str {
-    let mut start = 64;
+impl From<i64> for Snafu {
+    fn from(mut number: i64) -> Self {
+        let mut digits = Vec::new();
 
-    for byte in buf.iter_mut().rev() {
-        let digit = number % 5;
-        let mut carry = false;
-        *byte = match digit {
-            0..=2 => b'0' + digit as u8,
-            3 => {
-                carry = true;
-                b'='
-            }
-            4 => {
-                carry = true;
-                b'-'
-            }
-            _ => unreachable!(),
-        };
+        while number != 0 {
+            let digit = match number.rem_euclid(5) {
+                d @ 0..=2 => d,
+                d => d - 5,
+            };
 
-        number /= 5;
-        if carry {
-            number += 1;
+            digits.push(digit as i8);
+            number = (number - digit) / 5;
         }
-        start -= 1;
-        if number == 0 {
-            break;
+
+        Snafu { digits }
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let digits = s
+            .trim()
+            .bytes()
+            .rev()
+            .map(|b| match b {
+                b'0'..=b'2' => Ok((b - b'0') as i64),
+                b'-' => Ok(-1),
+                b'=' => Ok(-2),
+                _ => Err(eyre!("Invalid character")),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Normalising strips any leading zeros.
+        Ok(Snafu::from_unnormalised(digits))
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.digits.is_empty() {
+            return f.write_char('0');
+        }
+
+        for &digit in self.digits.iter().rev() {
+            let ch = match digit {
+                -2 => '=',
+                -1 => '-',
+                0 => '0',
+                1 => '1',
+                2 => '2',
+                _ => unreachable!(),
+            };
+            f.write_char(ch)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Add for &Snafu {
+    type Output = Snafu;
+
+    fn add(self, rhs: Self) -> Snafu {
+        let len = self.digits.len().max(rhs.digits.len());
+        let digit = |s: &Snafu, i: usize| s.digits.get(i).copied().unwrap_or(0) as i64;
+
+        Snafu::from_unnormalised((0..len).map(|i| digit(self, i) + digit(rhs, i)))
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, rhs: Self) -> Snafu {
+        &self + &rhs
+    }
+}
+
+impl Neg for &Snafu {
+    type Output = Snafu;
+
+    fn neg(self) -> Snafu {
+        Snafu {
+            digits: self.digits.iter().map(|d| -d).collect(),
         }
     }
+}
 
-    // SAFETY: We only put ASCII characters in the buffer.
-    unsafe { std::str::from_utf8_unchecked(&buf[start..]) }
+impl Neg for Snafu {
+    type Output = Snafu;
+
+    fn neg(self) -> Snafu {
+        -&self
+    }
 }
 
-fn parse(input: &str) -> Result<Vec<i64>> {
-    input.lines().map(str::trim).map(from_snafu).collect()
+impl Sub for &Snafu {
+    type Output = Snafu;
+
+    fn sub(self, rhs: Self) -> Snafu {
+        self + &-rhs
+    }
 }
 
-fn part1(data: &[i64]) -> String {
-    let mut buffer = [0; 64];
-    let number = data.iter().sum();
-    to_snafu(number, &mut buffer).to_owned()
+impl Sub for Snafu {
+    type Output = Snafu;
+
+    fn sub(self, rhs: Self) -> Snafu {
+        &self - &rhs
+    }
+}
+
+impl Mul for &Snafu {
+    type Output = Snafu;
+
+    fn mul(self, rhs: Self) -> Snafu {
+        if self.digits.is_empty() || rhs.digits.is_empty() {
+            return Snafu::default();
+        }
+
+        // Long multiplication, leaving the carrying until the end.
+        let mut raw = vec![0; self.digits.len() + rhs.digits.len()];
+        for (i, &a) in self.digits.iter().enumerate() {
+            for (j, &b) in rhs.digits.iter().enumerate() {
+                raw[i + j] += a as i64 * b as i64;
+            }
+        }
+
+        Snafu::from_unnormalised(raw)
+    }
+}
+
+impl Mul for Snafu {
+    type Output = Snafu;
+
+    fn mul(self, rhs: Self) -> Snafu {
+        &self * &rhs
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), |acc, n| &acc + &n)
+    }
+}
+
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Snafu>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), |acc, n| &acc + n)
+    }
+}
+
+pub type Parsed = Vec<Snafu>;
+
+pub fn parse(input: &str) -> Result<Parsed, DayError> {
+    input
+        .lines()
+        .map(|line| Ok(parse_at(input, line.trim(), "a SNAFU number")?))
+        .collect()
+}
+
+pub fn part1(data: &[Snafu]) -> String {
+    let number: i64 = data.iter().map(Snafu::to_i64).sum();
+    Snafu::from(number).to_string()
+}
+
+/// Solves part 1 from a reader, a number at a time.
+pub fn stream(reader: impl BufRead) -> Result<String, DayError> {
+    // Summing in SNAFU rather than an i64 means the total can't overflow, however long the input.
+    let mut total = Snafu::default();
+    input::for_each_line(reader, |line| {
+        total = &total + &parse_at::<Snafu>(line, line.trim(), "a SNAFU number")?;
+        Ok(())
+    })?;
+
+    Ok(total.to_string())
+}
+
+fn part1_digitwise(data: &[Snafu]) -> String {
+    data.iter().sum::<Snafu>().to_string()
 }
 
 #[cfg(test)]
@@ -84,6 +287,13 @@ mod tests {
     use super::*;
     use aoc_lib::Example;
 
+    fn example_pairs(data: &str) -> impl Iterator<Item = (i64, &str)> {
+        data.lines().map(|l| {
+            let Some((decimal, snafu)) = l.trim().split_once(' ') else { panic!("Invalid input") };
+            (decimal.parse::<i64>().unwrap(), snafu.trim())
+        })
+    }
+
     #[test]
     fn from_snafu_test() {
         let data = aoc_lib::input(DAY.day)
@@ -91,13 +301,8 @@ mod tests {
             .open()
             .unwrap();
 
-        let pairs = data.lines().map(|l| {
-            let Some((decimal, snafu)) = l.trim().split_once(' ') else { panic!("Invalid input") };
-            (decimal.parse::<i64>().unwrap(), snafu.trim())
-        });
-
-        for (decimal, snafu) in pairs {
-            let actual = from_snafu(snafu).unwrap();
+        for (decimal, snafu) in example_pairs(&data) {
+            let actual = snafu.parse::<Snafu>().unwrap().to_i64();
             assert_eq!(decimal, actual, "{snafu}");
         }
     }
@@ -109,15 +314,45 @@ mod tests {
             .open()
             .unwrap();
 
-        let pairs = data.lines().map(|l| {
-            let Some((decimal, snafu)) = l.trim().split_once(' ') else { panic!("Invalid input") };
-            (decimal.parse::<i64>().unwrap(), snafu.trim())
-        });
-
-        for (decimal, snafu) in pairs {
-            let mut buffer = [0; 64];
-            let actual = to_snafu(decimal, &mut buffer);
+        for (decimal, snafu) in example_pairs(&data) {
+            let actual = Snafu::from(decimal).to_string();
             assert_eq!(snafu, actual, "{decimal}");
         }
     }
+
+    #[test]
+    fn stream_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+        let numbers = parse(&data).unwrap();
+
+        assert_eq!(part1(&numbers), stream(data.as_bytes()).unwrap());
+
+        // Each number is over i64::MAX, and their sum is 5^28 - 1.
+        let big = "2".repeat(28);
+        let expected = format!("1{}-", "0".repeat(27));
+        let input = format!("{big}\n{big}\n");
+        assert_eq!(expected, stream(input.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Parse, 1)
+            .open()
+            .unwrap();
+
+        let numbers: Vec<i64> = example_pairs(&data).map(|(d, _)| d).collect();
+        for &a in &numbers {
+            for &b in &numbers {
+                let (sa, sb) = (Snafu::from(a), Snafu::from(b));
+                assert_eq!(Snafu::from(a + b), &sa + &sb, "{a} + {b}");
+                assert_eq!(Snafu::from(a - b), &sa - &sb, "{a} - {b}");
+                assert_eq!(Snafu::from(a * b), &sa * &sb, "{a} * {b}");
+                assert_eq!(Snafu::from(-a), -&sa, "-{a}");
+            }
+        }
+    }
 }
@@ -0,0 +1,140 @@
+//! Seeded generators for synthetic puzzle inputs.
+//!
+//! Each generator writes an input in its day's exact format, which the day's solver can parse and
+//! solve. The same day, size and seed always produce the same input, so a stress input can be
+//! recreated from its parameters alone. What `size` measures depends on the day, and is documented
+//! on each generator.
+
+use std::fmt;
+
+use color_eyre::eyre::eyre;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::DayError;
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+/// The random number generator passed to the generators. ChaCha's output is the same on every
+/// platform, which a seeded input needs.
+pub type Rng = ChaCha8Rng;
+
+type Generator = fn(&mut Rng, usize, &mut String) -> fmt::Result;
+
+/// Generates an input for the given day.
+pub fn generate(day: u8, size: usize, seed: u64) -> Result<String, DayError> {
+    let generator: Generator = match day {
+        1 => day01::generate,
+        2 => day02::generate,
+        3 => day03::generate,
+        4 => day04::generate,
+        5 => day05::generate,
+        6 => day06::generate,
+        7 => day07::generate,
+        8 => day08::generate,
+        9 => day09::generate,
+        10 => day10::generate,
+        11 => day11::generate,
+        12 => day12::generate,
+        13 => day13::generate,
+        14 => day14::generate,
+        15 => day15::generate,
+        16 => day16::generate,
+        17 => day17::generate,
+        18 => day18::generate,
+        19 => day19::generate,
+        20 => day20::generate,
+        21 => day21::generate,
+        22 => day22::generate,
+        23 => day23::generate,
+        24 => day24::generate,
+        25 => day25::generate,
+        _ => return Err(eyre!("No generator for day {}", day)),
+    };
+
+    let mut rng = Rng::seed_from_u64(seed);
+    let mut input = String::new();
+    generator(&mut rng, size, &mut input)?;
+
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+    use crate::{
+        days,
+        solution::{normalise, Solution},
+    };
+
+    fn check<S: Solution>(size: usize) {
+        let input = generate(S::DAY, size, 2022).unwrap();
+        assert_eq!(input, generate(S::DAY, size, 2022).unwrap());
+        assert_ne!(input, generate(S::DAY, size, 2023).unwrap());
+
+        // Generated inputs should already be normalised, and have the shape the day expects.
+        let normalised = normalise::<S>(&input).unwrap_or_else(|e| panic!("Day {}: {e}", S::DAY));
+        assert!(matches!(normalised, Cow::Borrowed(_)), "Day {}", S::DAY);
+
+        let data = S::parse(&input).unwrap_or_else(|e| panic!("Day {}: {e}", S::DAY));
+        S::part1(&data).unwrap_or_else(|e| panic!("Day {} part 1: {e}", S::DAY));
+        if S::HAS_PART_2 {
+            S::part2(&data).unwrap_or_else(|e| panic!("Day {} part 2: {e}", S::DAY));
+        }
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        check::<days::day01::Day01>(100);
+        check::<days::day02::Day02>(100);
+        check::<days::day03::Day03>(30);
+        check::<days::day04::Day04>(100);
+        check::<days::day05::Day05>(100);
+        check::<days::day06::Day06>(1000);
+        check::<days::day07::Day07>(50);
+        check::<days::day08::Day08>(20);
+        check::<days::day09::Day09>(100);
+        check::<days::day10::Day10>(240);
+        check::<days::day11::Day11>(20);
+        check::<days::day12::Day12>(40);
+        check::<days::day13::Day13>(50);
+        check::<days::day14::Day14>(20);
+        check::<days::day15::Day15>(10);
+        check::<days::day16::Day16>(8);
+        check::<days::day17::Day17>(50);
+        check::<days::day18::Day18>(8);
+        check::<days::day19::Day19>(3);
+        check::<days::day20::Day20>(100);
+        check::<days::day21::Day21>(100);
+        check::<days::day22::Day22>(4);
+        check::<days::day23::Day23>(10);
+        check::<days::day24::Day24>(20);
+        check::<days::day25::Day25>(100);
+    }
+}
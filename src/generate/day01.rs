@@ -0,0 +1,20 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes `size` elves, each carrying up to fifteen items.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    for elf in 0..size {
+        if elf > 0 {
+            writeln!(out)?;
+        }
+
+        for _ in 0..rng.gen_range(1..=15) {
+            writeln!(out, "{}", rng.gen_range(1000..=70000))?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes `size` rounds of the strategy guide.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    for _ in 0..size {
+        let theirs = rng.gen_range(b'A'..=b'C') as char;
+        let ours = rng.gen_range(b'X'..=b'Z') as char;
+        writeln!(out, "{theirs} {ours}")?;
+    }
+
+    Ok(())
+}
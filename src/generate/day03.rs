@@ -0,0 +1,51 @@
+use std::fmt::{self, Write};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Writes `size` groups of three rucksacks.
+///
+/// Each rucksack has exactly one item type in both compartments, and each group has exactly one
+/// item type, its badge, carried by all three elves.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    for _ in 0..size {
+        let mut items = ITEMS.to_vec();
+        items.shuffle(rng);
+        let (&badge, items) = items.split_last().unwrap();
+
+        // Giving each elf their own items means the badge is the only one they all share.
+        for pool in items.chunks_exact(17) {
+            let shared = if rng.gen_bool(0.2) { badge } else { pool[16] };
+
+            let len = rng.gen_range(4..=16);
+            let mut left = compartment(rng, len, shared, &pool[..8]);
+            let mut right = compartment(rng, len, shared, &pool[8..16]);
+
+            if shared != badge {
+                // The badge goes in only one compartment, so it isn't shared between them.
+                let compartment = if rng.gen_bool(0.5) {
+                    &mut left
+                } else {
+                    &mut right
+                };
+                let slot = compartment.iter().position(|&i| i != shared).unwrap();
+                compartment[slot] = badge;
+            }
+
+            out.extend(left.into_iter().chain(right).map(char::from));
+            writeln!(out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fills a compartment with `len` items from `pool`, and one `shared` item.
+fn compartment(rng: &mut Rng, len: usize, shared: u8, pool: &[u8]) -> Vec<u8> {
+    let mut items: Vec<u8> = (1..len).map(|_| *pool.choose(rng).unwrap()).collect();
+    items.insert(rng.gen_range(0..len), shared);
+    items
+}
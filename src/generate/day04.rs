@@ -0,0 +1,21 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes `size` pairs of section assignments.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let mut range = || {
+        let start = rng.gen_range(1..=99);
+        (start, rng.gen_range(start..=99))
+    };
+
+    for _ in 0..size {
+        let (a, b) = range();
+        let (c, d) = range();
+        writeln!(out, "{a}-{b},{c}-{d}")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+const NUM_STACKS: usize = 9;
+
+/// Writes a drawing of nine stacks of crates, followed by `size` rearrangement steps.
+///
+/// The steps are simulated as they're generated, so they never move more crates than a stack
+/// holds.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let mut stacks: Vec<Vec<u8>> = (0..NUM_STACKS)
+        .map(|_| {
+            (0..rng.gen_range(1..=8))
+                .map(|_| rng.gen_range(b'A'..=b'Z'))
+                .collect()
+        })
+        .collect();
+
+    let height = stacks.iter().map(Vec::len).max().unwrap();
+    for level in (0..height).rev() {
+        for (id, stack) in stacks.iter().enumerate() {
+            if id > 0 {
+                out.push(' ');
+            }
+            match stack.get(level) {
+                Some(&krate) => write!(out, "[{}]", krate as char)?,
+                None => out.push_str("   "),
+            }
+        }
+        writeln!(out)?;
+    }
+
+    for id in 1..=NUM_STACKS {
+        if id > 1 {
+            out.push(' ');
+        }
+        write!(out, " {id} ")?;
+    }
+    writeln!(out)?;
+    writeln!(out)?;
+
+    for _ in 0..size {
+        let from = loop {
+            let from = rng.gen_range(0..NUM_STACKS);
+            if !stacks[from].is_empty() {
+                break from;
+            }
+        };
+        let to = (from + rng.gen_range(1..NUM_STACKS)) % NUM_STACKS;
+        let count = rng.gen_range(1..=stacks[from].len().min(20));
+
+        let remaining = stacks[from].len() - count;
+        let moved = stacks[from].split_off(remaining);
+        stacks[to].extend(moved);
+
+        writeln!(out, "move {count} from {} to {}", from + 1, to + 1)?;
+    }
+
+    Ok(())
+}
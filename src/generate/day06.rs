@@ -0,0 +1,24 @@
+use std::fmt;
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+/// Writes a datastream `size` characters long.
+///
+/// Most of it is drawn from only a few letters, so the start-of-message marker, which needs
+/// fourteen different letters, is placed somewhere in the second half.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let size = size.max(14);
+    let mut signal: Vec<u8> = (0..size).map(|_| rng.gen_range(b'a'..=b'e')).collect();
+
+    let mut marker: Vec<u8> = (b'a'..=b'z').collect();
+    marker.shuffle(rng);
+    let start = rng.gen_range(size / 2..=size - 14);
+    signal[start..start + 14].copy_from_slice(&marker[..14]);
+
+    out.extend(signal.into_iter().map(char::from));
+    out.push('\n');
+
+    Ok(())
+}
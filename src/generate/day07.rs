@@ -0,0 +1,94 @@
+use std::fmt::{self, Write};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+const EXTENSIONS: &[&str] = &["", ".txt", ".dat", ".log", ".bin"];
+
+#[derive(Default)]
+struct Directory {
+    name: String,
+    files: Vec<(String, u64)>,
+    children: Vec<usize>,
+}
+
+/// Writes the terminal output of exploring a filesystem with `size` directories besides the root.
+///
+/// The file sizes are scaled so that the disk is between 41M and 69M full, which the puzzle needs
+/// for there to be a directory worth deleting.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let mut dirs = vec![Directory::default()];
+
+    for id in 1..=size {
+        let parent = rng.gen_range(0..id);
+        let name = unique_name(rng, &dirs, parent, "");
+        dirs[parent].children.push(id);
+        dirs.push(Directory {
+            name,
+            ..Default::default()
+        });
+    }
+
+    let mut total = 0;
+    for id in 0..dirs.len() {
+        for _ in 0..rng.gen_range(1..=4) {
+            let ext = EXTENSIONS.choose(rng).unwrap();
+            let name = unique_name(rng, &dirs, id, ext);
+            let file_size = rng.gen_range(1000..=300_000);
+            total += file_size;
+            dirs[id].files.push((name, file_size));
+        }
+    }
+
+    // Scale the sizes to fill the disk, putting any rounding error in the last file.
+    let target = rng.gen_range(41_000_000..=69_000_000);
+    let mut scaled_total = 0;
+    for (_, file_size) in dirs.iter_mut().flat_map(|d| &mut d.files) {
+        *file_size = (*file_size * target / total).max(1);
+        scaled_total += *file_size;
+    }
+    if let Some((_, file_size)) = dirs.iter_mut().flat_map(|d| &mut d.files).last() {
+        *file_size += target.saturating_sub(scaled_total);
+    }
+
+    writeln!(out, "$ cd /")?;
+    write_dir(&dirs, 0, out)
+}
+
+fn write_dir(dirs: &[Directory], id: usize, out: &mut String) -> fmt::Result {
+    let dir = &dirs[id];
+
+    writeln!(out, "$ ls")?;
+    for &child in &dir.children {
+        writeln!(out, "dir {}", dirs[child].name)?;
+    }
+    for (name, size) in &dir.files {
+        writeln!(out, "{size} {name}")?;
+    }
+
+    for &child in &dir.children {
+        writeln!(out, "$ cd {}", dirs[child].name)?;
+        write_dir(dirs, child, out)?;
+        writeln!(out, "$ cd ..")?;
+    }
+
+    Ok(())
+}
+
+/// Picks a name which isn't already used by anything in the directory.
+fn unique_name(rng: &mut Rng, dirs: &[Directory], id: usize, ext: &str) -> String {
+    let dir = &dirs[id];
+    loop {
+        let mut name: String = (0..rng.gen_range(1..=8))
+            .map(|_| rng.gen_range(b'a'..=b'z') as char)
+            .collect();
+        name.push_str(ext);
+
+        let is_dir = dir.children.iter().any(|&c| dirs[c].name == name);
+        let is_file = dir.files.iter().any(|(n, _)| *n == name);
+        if !is_dir && !is_file {
+            return name;
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes a square forest `size` trees wide.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let size = size.max(1);
+    for _ in 0..size {
+        for _ in 0..size {
+            out.push((b'0' + rng.gen_range(0..=9)) as char);
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
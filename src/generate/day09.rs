@@ -0,0 +1,15 @@
+use std::fmt::{self, Write};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+/// Writes `size` moves of the rope's head.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    for _ in 0..size {
+        let dir = ["U", "D", "L", "R"].choose(rng).unwrap();
+        writeln!(out, "{dir} {}", rng.gen_range(1..=20))?;
+    }
+
+    Ok(())
+}
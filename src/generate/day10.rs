@@ -0,0 +1,19 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes `size` instructions. There are always at least 240, so the program lasts long enough to
+/// draw the whole screen.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    for _ in 0..size.max(240) {
+        if rng.gen_bool(0.3) {
+            writeln!(out, "noop")?;
+        } else {
+            writeln!(out, "addx {}", rng.gen_range(-10..=10))?;
+        }
+    }
+
+    Ok(())
+}
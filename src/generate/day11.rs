@@ -0,0 +1,64 @@
+use std::fmt::{self, Write};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+const NUM_MONKEYS: usize = 8;
+
+/// Writes eight monkeys, holding `size` items between them, with each monkey holding at least
+/// one.
+///
+/// Each monkey tests against a different prime. The solver keeps worry levels below the product
+/// of the primes, and squares them, so the number of monkeys is fixed to keep that within a
+/// `u64`.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let mut primes = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    primes.shuffle(rng);
+
+    let mut items = [1; NUM_MONKEYS];
+    for _ in NUM_MONKEYS..size {
+        items[rng.gen_range(0..NUM_MONKEYS)] += 1;
+    }
+
+    let squarer = rng.gen_range(0..NUM_MONKEYS);
+
+    for (id, (&num_items, &test_div)) in items.iter().zip(&primes).enumerate() {
+        if id > 0 {
+            writeln!(out)?;
+        }
+
+        writeln!(out, "Monkey {id}:")?;
+
+        write!(out, "  Starting items: ")?;
+        for item in 0..num_items {
+            if item > 0 {
+                write!(out, ", ")?;
+            }
+            write!(out, "{}", rng.gen_range(50..=99))?;
+        }
+        writeln!(out)?;
+
+        if id == squarer {
+            writeln!(out, "  Operation: new = old * old")?;
+        } else if rng.gen_bool(0.25) {
+            writeln!(out, "  Operation: new = old * {}", rng.gen_range(2..=19))?;
+        } else {
+            writeln!(out, "  Operation: new = old + {}", rng.gen_range(1..=8))?;
+        }
+
+        writeln!(out, "  Test: divisible by {test_div}")?;
+
+        let if_true = (id + rng.gen_range(1..NUM_MONKEYS)) % NUM_MONKEYS;
+        let if_false = loop {
+            let target = (id + rng.gen_range(1..NUM_MONKEYS)) % NUM_MONKEYS;
+            if target != if_true {
+                break target;
+            }
+        };
+        writeln!(out, "    If true: throw to monkey {if_true}")?;
+        writeln!(out, "    If false: throw to monkey {if_false}")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes a heightmap `size` squares wide and a quarter as tall.
+///
+/// The start is on the left edge and the goal on the right, with a gentle climb between them along
+/// their row so there is always a route. The rest of the map is noise around the same slope.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let width = size.max(26);
+    let height = (width / 4).max(5);
+    let route = rng.gen_range(0..height);
+
+    // Climbing one step at most per square, which needs at least 26 squares.
+    let slope = |x: usize| (x * 25 / (width - 1)) as i32;
+
+    for y in 0..height {
+        for x in 0..width {
+            let square = if y == route && x == 0 {
+                'S'
+            } else if y == route && x == width - 1 {
+                'E'
+            } else if y == route {
+                (b'a' + slope(x) as u8) as char
+            } else {
+                let noisy = slope(x) + rng.gen_range(-4..=4);
+                (b'a' + noisy.clamp(0, 25) as u8) as char
+            };
+            out.push(square);
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes `size` pairs of packets. The two packets of a pair are never identical.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    for pair in 0..size {
+        if pair > 0 {
+            writeln!(out)?;
+        }
+
+        let left = packet(rng);
+        let right = loop {
+            let right = packet(rng);
+            if right != left {
+                break right;
+            }
+        };
+        writeln!(out, "{left}")?;
+        writeln!(out, "{right}")?;
+    }
+
+    Ok(())
+}
+
+fn packet(rng: &mut Rng) -> String {
+    let mut packet = String::new();
+    write_list(rng, 0, &mut packet);
+    packet
+}
+
+fn write_list(rng: &mut Rng, depth: usize, out: &mut String) {
+    out.push('[');
+    for item in 0..rng.gen_range(0..=5) {
+        if item > 0 {
+            out.push(',');
+        }
+
+        if depth < 4 && rng.gen_bool(0.3) {
+            write_list(rng, depth + 1, out);
+        } else {
+            out.push_str(&rng.gen_range(0..=10).to_string());
+        }
+    }
+    out.push(']');
+}
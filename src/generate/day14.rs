@@ -0,0 +1,33 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes `size` paths of rock, each with up to five points.
+///
+/// The paths are kept below and around the sand's source, similar to the puzzle's, so the cave
+/// stays a reasonable size.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    for _ in 0..size.max(1) {
+        let mut x: i32 = rng.gen_range(450..=550);
+        let mut y: i32 = rng.gen_range(10..=170);
+        write!(out, "{x},{y}")?;
+
+        let mut horizontal = rng.gen_bool(0.5);
+        for _ in 1..rng.gen_range(2..=5) {
+            let step = rng.gen_range(1..=10) * if rng.gen_bool(0.5) { 1 } else { -1 };
+            if horizontal {
+                x = (x + step).clamp(450, 550);
+            } else {
+                y = (y + step).clamp(10, 170);
+            }
+            horizontal = !horizontal;
+
+            write!(out, " -> {x},{y}")?;
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
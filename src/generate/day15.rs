@@ -0,0 +1,58 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+const RANGE: i32 = crate::days::day15::SEARCH_BOUND;
+
+/// Writes `size` sensors scattered around the search area, plus eight which hide the distress
+/// beacon.
+///
+/// The distress beacon is put at a random point, then surrounded by eight sensors with huge
+/// ranges. Four of them cover everything in line with the beacon, and four everything diagonal to
+/// it, leaving only that one point uncovered in the search area. The scattered sensors' ranges
+/// stop short of the beacon.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let hidden = (rng.gen_range(1..RANGE), rng.gen_range(1..RANGE));
+
+    let reach = RANGE + 1;
+    let diagonal = RANGE / 2 + 1;
+    for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+        let sensor = (hidden.0 + dx * reach, hidden.1 + dy * reach);
+        write_sensor(out, sensor, reach - 1, (-dx, -dy))?;
+    }
+    for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+        let sensor = (hidden.0 + dx * diagonal, hidden.1 + dy * diagonal);
+        write_sensor(out, sensor, 2 * diagonal - 1, (-dx, 0))?;
+    }
+
+    for _ in 0..size {
+        let sensor = loop {
+            let sensor = (rng.gen_range(0..=RANGE), rng.gen_range(0..=RANGE));
+            if sensor != hidden {
+                break sensor;
+            }
+        };
+        let distance = (sensor.0 - hidden.0).abs() + (sensor.1 - hidden.1).abs();
+        let dir = match rng.gen_range(0..4) {
+            0 => (1, 0),
+            1 => (-1, 0),
+            2 => (0, 1),
+            _ => (0, -1),
+        };
+        write_sensor(out, sensor, rng.gen_range(0..distance), dir)?;
+    }
+
+    Ok(())
+}
+
+/// Writes a sensor with its closest beacon `range` away in the direction `dir`.
+fn write_sensor(out: &mut String, sensor: (i32, i32), range: i32, dir: (i32, i32)) -> fmt::Result {
+    let beacon = (sensor.0 + dir.0 * range, sensor.1 + dir.1 * range);
+    writeln!(
+        out,
+        "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+        sensor.0, sensor.1, beacon.0, beacon.1
+    )
+}
@@ -0,0 +1,69 @@
+use std::fmt::{self, Write};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+/// Writes a scan of `size` valves, capped at 676 by the two-letter names.
+///
+/// At most fifteen valves have a flow rate, the same as the puzzle, as the search is exponential
+/// in how many there are. The tunnels always connect every valve to `AA`.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let mut names: Vec<String> = (b'A'..=b'Z')
+        .flat_map(|a| (b'A'..=b'Z').map(move |b| format!("{}{}", a as char, b as char)))
+        .filter(|name| name != "AA")
+        .collect();
+    names.shuffle(rng);
+    names.truncate(size.clamp(2, 676) - 1);
+    names.insert(0, "AA".to_owned());
+
+    let num_valves = names.len();
+    let mut tunnels = vec![Vec::new(); num_valves];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+
+    // A random tree keeps everything connected, then some extra tunnels add loops.
+    for valve in 1..num_valves {
+        connect(valve, rng.gen_range(0..valve));
+    }
+    for _ in 0..num_valves / 2 {
+        connect(rng.gen_range(0..num_valves), rng.gen_range(0..num_valves));
+    }
+
+    let mut flow_rates = vec![0; num_valves];
+    let mut useful: Vec<usize> = (1..num_valves).collect();
+    useful.shuffle(rng);
+    for &valve in useful.iter().take(15) {
+        flow_rates[valve] = rng.gen_range(1..=25);
+    }
+
+    let mut order: Vec<usize> = (0..num_valves).collect();
+    order.shuffle(rng);
+
+    for valve in order {
+        write!(
+            out,
+            "Valve {} has flow rate={}; ",
+            names[valve], flow_rates[valve]
+        )?;
+
+        if let [tunnel] = tunnels[valve][..] {
+            writeln!(out, "tunnel leads to valve {}", names[tunnel])?;
+        } else {
+            write!(out, "tunnels lead to valves ")?;
+            for (idx, &tunnel) in tunnels[valve].iter().enumerate() {
+                if idx > 0 {
+                    write!(out, ", ")?;
+                }
+                write!(out, "{}", names[tunnel])?;
+            }
+            writeln!(out)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes a jet pattern `size` jets long.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    for _ in 0..size.max(1) {
+        out.push(if rng.gen_bool(0.5) { '<' } else { '>' });
+    }
+    writeln!(out)
+}
@@ -0,0 +1,22 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes the cubes of a droplet filling about a third of a `size`-wide cube. There is always at
+/// least one cube.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let size = size.max(1);
+    for x in 0..size {
+        for y in 0..size {
+            for z in 0..size {
+                if rng.gen_bool(0.35) || (x, y, z) == (0, 0, 0) {
+                    writeln!(out, "{x},{y},{z}")?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes `size` blueprints, with costs in the same ranges as the puzzle's.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    for id in 1..=size {
+        writeln!(
+            out,
+            "Blueprint {id}: \
+            Each ore robot costs {} ore. \
+            Each clay robot costs {} ore. \
+            Each obsidian robot costs {} ore and {} clay. \
+            Each geode robot costs {} ore and {} obsidian.",
+            rng.gen_range(2..=4),
+            rng.gen_range(2..=4),
+            rng.gen_range(2..=4),
+            rng.gen_range(5..=20),
+            rng.gen_range(2..=4),
+            rng.gen_range(7..=20),
+        )?;
+    }
+
+    Ok(())
+}
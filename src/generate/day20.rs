@@ -0,0 +1,23 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes an encrypted file of `size` numbers, exactly one of which is zero.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let size = size.max(1);
+    let zero = rng.gen_range(0..size);
+    for idx in 0..size {
+        let number = if idx == zero {
+            0
+        } else if rng.gen_bool(0.5) {
+            rng.gen_range(1..=10000)
+        } else {
+            -rng.gen_range(1..=10000)
+        };
+        writeln!(out, "{number}")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Write},
+};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+struct Builder<'a> {
+    rng: &'a mut Rng,
+    names: HashSet<String>,
+    jobs: Vec<String>,
+}
+
+impl Builder<'_> {
+    fn name(&mut self) -> String {
+        loop {
+            let name: String = (0..4)
+                .map(|_| self.rng.gen_range(b'a'..=b'z') as char)
+                .collect();
+            if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    /// Adds a monkey which yells `value`, along with roughly `size` monkeys it depends on.
+    ///
+    /// Working down from the value keeps every division exact, and every number positive.
+    fn subtree(&mut self, value: i64, size: usize) -> String {
+        let name = self.name();
+        if size < 3 {
+            self.jobs.push(format!("{name}: {value}"));
+            return name;
+        }
+
+        let divisor = (2..=10).find(|d| value % d == 0);
+        let (left, op, right) = match (self.rng.gen_range(0..4), divisor) {
+            (0, _) if value >= 2 => {
+                let left = self.rng.gen_range(1..value);
+                (left, '+', value - left)
+            }
+            (1, Some(divisor)) => (value / divisor, '*', divisor),
+            (2, _) if value <= 10_000 => {
+                let divisor = self.rng.gen_range(2..=5);
+                (value * divisor, '/', divisor)
+            }
+            _ => {
+                let right = self.rng.gen_range(1..=10);
+                (value + right, '-', right)
+            }
+        };
+
+        let left_size = self.rng.gen_range(1..size - 1);
+        let left = self.subtree(left, left_size);
+        let right = self.subtree(right, size - 1 - left_size);
+        self.jobs.push(format!("{name}: {left} {op} {right}"));
+        name
+    }
+}
+
+/// Writes the jobs of roughly `size` monkeys.
+///
+/// `humn` appears once, at the bottom of a chain of additions and subtractions from `root`, so
+/// there's always a whole number for it to yell for `root`'s two sides to match.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let size = size.max(5);
+    let depth = (size / 10).clamp(1, 50);
+    let mut builder = Builder {
+        rng,
+        names: HashSet::new(),
+        jobs: Vec::new(),
+    };
+
+    // Each monkey on the chain from `root` to `humn` has another subtree beside the chain.
+    let num_subtrees = depth + 1;
+    let subtree_size = ((size - depth - 2) / num_subtrees).max(1);
+
+    let mut child = "humn".to_owned();
+    let humn = builder.rng.gen_range(1..=1000);
+    builder.jobs.push(format!("humn: {humn}"));
+
+    for _ in 0..depth {
+        let other_value = builder.rng.gen_range(1..=1000);
+        let other = builder.subtree(other_value, subtree_size);
+        let name = builder.name();
+
+        let job = match builder.rng.gen_range(0..3) {
+            0 => format!("{name}: {child} + {other}"),
+            1 => format!("{name}: {child} - {other}"),
+            _ => format!("{name}: {other} - {child}"),
+        };
+        builder.jobs.push(job);
+        child = name;
+    }
+
+    let other_value = builder.rng.gen_range(1..=1000);
+    let other = builder.subtree(other_value, subtree_size);
+    let job = if builder.rng.gen_bool(0.5) {
+        format!("root: {child} + {other}")
+    } else {
+        format!("root: {other} + {child}")
+    };
+    builder.jobs.push(job);
+
+    let Builder { rng, mut jobs, .. } = builder;
+    jobs.shuffle(rng);
+    for job in jobs {
+        writeln!(out, "{job}")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use std::fmt::{self, Write};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+/// The eleven nets of a cube, with `#` marking a face.
+const NETS: &[&[&str]] = &[
+    &["#...", "####", "#..."],
+    &["#...", "####", ".#.."],
+    &["#...", "####", "..#."],
+    &["#...", "####", "...#"],
+    &[".#..", "####", ".#.."],
+    &[".#..", "####", "..#."],
+    &["##..", ".###", ".#.."],
+    &["##..", ".###", "..#."],
+    &["##..", ".###", "...#"],
+    &["##..", ".##.", "..##"],
+    &["###..", "..###"],
+];
+
+/// Writes a map whose faces are each `size` tiles square, followed by a path of `size` * 40
+/// moves.
+///
+/// The map is one of the nets of a cube, randomly turned and flipped, so it can be walked both
+/// flat and folded.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let size = size.max(1);
+
+    let mut net: Vec<Vec<bool>> = NETS
+        .choose(rng)
+        .unwrap()
+        .iter()
+        .map(|row| row.bytes().map(|b| b == b'#').collect())
+        .collect();
+    for _ in 0..rng.gen_range(0..4) {
+        net = (0..net[0].len())
+            .map(|x| net.iter().rev().map(|row| row[x]).collect())
+            .collect();
+    }
+    if rng.gen_bool(0.5) {
+        net.iter_mut().for_each(|row| row.reverse());
+    }
+
+    for (net_y, net_row) in net.iter().enumerate() {
+        for y in 0..size {
+            let mut row = String::new();
+            for &is_face in net_row {
+                for x in 0..size {
+                    // Keeping the top-left of each face open leaves the start tile open.
+                    let is_start = net_y == 0 && y == 0 && x == 0;
+                    row.push(match is_face {
+                        false => ' ',
+                        true if !is_start && rng.gen_bool(0.1) => '#',
+                        true => '.',
+                    });
+                }
+            }
+            writeln!(out, "{}", row.trim_end())?;
+        }
+    }
+    writeln!(out)?;
+
+    let moves = size * 40;
+    for step in 0..moves {
+        write!(out, "{}", rng.gen_range(1..=50))?;
+        if step + 1 < moves {
+            out.push(if rng.gen_bool(0.5) { 'L' } else { 'R' });
+        }
+    }
+    writeln!(out)
+}
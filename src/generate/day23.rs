@@ -0,0 +1,24 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes a scan `size` tiles square, about half of which hold an elf. There is always at least
+/// one elf.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let size = size.max(1);
+    let lone_elf = (rng.gen_range(0..size), rng.gen_range(0..size));
+    for y in 0..size {
+        for x in 0..size {
+            out.push(if rng.gen_bool(0.5) || (x, y) == lone_elf {
+                '#'
+            } else {
+                '.'
+            });
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
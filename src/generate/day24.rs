@@ -0,0 +1,33 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// Writes a valley `size` squares wide and a quarter as tall, capped at 128 by 32 squares.
+///
+/// As in the puzzle, there are no up or down blizzards in the columns of the entrance and exit,
+/// which would otherwise blow out of the valley.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let width = size.clamp(3, 128);
+    let height = (width / 4).max(2);
+
+    writeln!(out, "#.{}", "#".repeat(width))?;
+    for _ in 0..height {
+        out.push('#');
+        for x in 0..width {
+            let vertical = x != 0 && x != width - 1;
+            let tile = match rng.gen_range(0..8) {
+                0..=2 => '.',
+                3..=4 => '<',
+                5..=6 => '>',
+                7 if vertical && rng.gen_bool(0.5) => '^',
+                7 if vertical => 'v',
+                _ => '.',
+            };
+            out.push(tile);
+        }
+        writeln!(out, "#")?;
+    }
+    writeln!(out, "{}.#", "#".repeat(width))
+}
@@ -0,0 +1,20 @@
+use std::fmt::{self, Write};
+
+use rand::Rng as _;
+
+use crate::days::day25::Snafu;
+
+use super::Rng;
+
+/// Writes `size` fuel requirements. Each is at most twenty SNAFU digits long, and they are kept
+/// small enough that their sum fits in an `i64`.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut String) -> fmt::Result {
+    let max = 5i64.pow(20).min(i64::MAX / size.max(1) as i64);
+    for _ in 0..size {
+        let digits = rng.gen_range(1..=20);
+        let number = rng.gen_range(1..=5i64.pow(digits).min(max));
+        writeln!(out, "{}", Snafu::from(number))?;
+    }
+
+    Ok(())
+}
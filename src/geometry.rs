@@ -0,0 +1,296 @@
+//! Points and vectors on a 2D integer grid, shared by the days which move things around a map.
+//!
+//! A [`Point2`] is a position, and a [`Vec2`] is the offset between two positions, so a point plus
+//! a vector is a point, and the difference of two points is a vector. As with [`crate::grid`],
+//! `y` increases downwards unless a day says otherwise.
+
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// The integer types a point or vector can be made of.
+pub trait Coord:
+    Copy + Debug + Default + Ord + Hash + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// The absolute difference between two values, which doesn't underflow for unsigned types.
+    fn difference(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+macro_rules! impl_coord {
+    ($($ty:ty),*) => {
+        $(
+            impl Coord for $ty {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+            }
+        )*
+    };
+}
+
+impl_coord!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts to a point made of a wider type, such as before a calculation which could
+    /// overflow the original.
+    pub fn cast<U: From<T>>(self) -> Point2<U> {
+        Point2::new(self.x.into(), self.y.into())
+    }
+}
+
+impl<T: Coord> Point2<T> {
+    /// The distance to `other` moving only orthogonally.
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.difference(other.x) + self.y.difference(other.y)
+    }
+
+    /// The distance to `other` when diagonal moves are allowed.
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.difference(other.x).max(self.y.difference(other.y))
+    }
+
+    /// The points above, left, right and below this one.
+    pub fn neighbours4(self) -> [Self; 4]
+    where
+        T: Neg<Output = T>,
+    {
+        Direction::ALL.map(|d| self + d.offset())
+    }
+}
+
+impl<T: Coord> Add<Vec2<T>> for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Vec2<T>) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Coord> AddAssign<Vec2<T>> for Point2<T> {
+    fn add_assign(&mut self, rhs: Vec2<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Coord> Sub<Vec2<T>> for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Vec2<T>) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Coord> SubAssign<Vec2<T>> for Point2<T> {
+    fn sub_assign(&mut self, rhs: Vec2<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Coord> Sub for Point2<T> {
+    type Output = Vec2<T>;
+
+    fn sub(self, rhs: Self) -> Vec2<T> {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Coord> Vec2<T> {
+    pub fn manhattan(self) -> T {
+        self.x.difference(T::ZERO) + self.y.difference(T::ZERO)
+    }
+
+    pub fn chebyshev(self) -> T {
+        self.x.difference(T::ZERO).max(self.y.difference(T::ZERO))
+    }
+
+    /// Clamps each component to -1, 0 or 1, giving a single step in the direction of the vector.
+    pub fn signum(self) -> Self
+    where
+        T: Neg<Output = T>,
+    {
+        Self::new(self.x.clamp(-T::ONE, T::ONE), self.y.clamp(-T::ONE, T::ONE))
+    }
+
+    pub fn scale(self, factor: T) -> Self {
+        self * factor
+    }
+}
+
+impl<T: Coord> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Coord> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Coord> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Coord + Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/// The four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Left,
+    Right,
+    Down,
+}
+
+impl Direction {
+    /// Every direction, in reading order of the cells they point to.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Left,
+        Direction::Right,
+        Direction::Down,
+    ];
+
+    /// A single step in this direction.
+    pub fn offset<T: Coord + Neg<Output = T>>(self) -> Vec2<T> {
+        match self {
+            Direction::Up => Vec2::new(T::ZERO, -T::ONE),
+            Direction::Left => Vec2::new(-T::ONE, T::ZERO),
+            Direction::Right => Vec2::new(T::ONE, T::ZERO),
+            Direction::Down => Vec2::new(T::ZERO, T::ONE),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+        }
+    }
+}
+
+/// The smallest rectangle containing a set of points, including its edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Coord> BoundingBox<T> {
+    /// Returns `None` if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Point2<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = Self {
+            min: first,
+            max: first,
+        };
+        points.for_each(|p| bounds.include(p));
+
+        Some(bounds)
+    }
+
+    /// Grows the box to contain `point`.
+    pub fn include(&mut self, point: Point2<T>) {
+        self.min = Point2::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point2::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn contains(&self, point: Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_test() {
+        let a = Point2::new(1u8, 7);
+        let b = Point2::new(4u8, 2);
+
+        assert_eq!(8, a.manhattan(b));
+        assert_eq!(8, b.manhattan(a));
+        assert_eq!(5, a.chebyshev(b));
+        assert_eq!(Vec2::new(3i16, -5), b.cast::<i16>() - a.cast::<i16>());
+    }
+
+    #[test]
+    fn signum_test() {
+        let step = (Point2::new(5i16, -3) - Point2::new(0, 0)).signum();
+        assert_eq!(Vec2::new(1, -1), step);
+        assert_eq!(Vec2::new(0i16, 0), Vec2::default().signum());
+        assert_eq!(Vec2::new(6i16, -2), Vec2::new(3, -1).scale(2));
+    }
+
+    #[test]
+    fn bounding_box_test() {
+        let points = [Point2::new(2i32, -1), Point2::new(-3, 4), Point2::new(0, 0)];
+        let bounds = BoundingBox::from_points(points).unwrap();
+
+        assert_eq!(Point2::new(-3, -1), bounds.min);
+        assert_eq!(Point2::new(2, 4), bounds.max);
+        assert_eq!((6, 6), (bounds.width(), bounds.height()));
+        assert!(bounds.contains(Point2::new(0, 3)));
+        assert!(!bounds.contains(Point2::new(3, 0)));
+        assert!(BoundingBox::<i32>::from_points([]).is_none());
+    }
+}
@@ -0,0 +1,278 @@
+//! A rectangular grid of cells, stored row by row.
+//!
+//! Cells are addressed by `(x, y)`, with `(0, 0)` the top-left corner and `y` increasing
+//! downwards, matching the way the puzzle maps are drawn.
+
+use std::{
+    fmt::{self, Display},
+    iter::StepBy,
+    ops::{Index, IndexMut},
+    slice::{self, ChunksExact},
+};
+
+use crate::input::ParseError;
+
+const ORTHOGONAL: &[(isize, isize)] = &[(0, -1), (-1, 0), (1, 0), (0, 1)];
+const ALL_DIRECTIONS: &[(isize, isize)] = &[
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses a map with one character per cell, such as the input to a grid day.
+    ///
+    /// `cell` is given the position and byte of each cell, and returns `None` if the byte isn't
+    /// valid, in which case the error points at it and says `expected` was wanted instead.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut((usize, usize), u8) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let width = input.lines().next().map_or(0, str::len);
+        if width == 0 {
+            return Err(ParseError::at_offset(input, 0, 0, "a row of the map"));
+        }
+
+        let mut cells = Vec::with_capacity(input.len());
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let line_start = line.as_ptr() as usize - input.as_ptr() as usize;
+            if line.len() != width {
+                let (offset, len) = if line.len() > width {
+                    (width, line.len() - width)
+                } else {
+                    (line.len(), 0)
+                };
+                let expected = format!("a row {width} wide");
+                return Err(ParseError::at_offset(
+                    input,
+                    line_start + offset,
+                    len,
+                    expected,
+                ));
+            }
+
+            for (x, byte) in line.bytes().enumerate() {
+                let Some(value) = cell((x, y), byte) else {
+                    return Err(ParseError::at_offset(input, line_start + x, 1, expected));
+                };
+                cells.push(value);
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.contains(x, y) {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at `(x, y)` without checking it's in the grid.
+    ///
+    /// # Safety
+    ///
+    /// `x` must be less than the width, and `y` less than the height.
+    pub unsafe fn get_unchecked(&self, x: usize, y: usize) -> &T {
+        self.cells.get_unchecked(y * self.width + x)
+    }
+
+    /// Adds a row to the bottom of the grid, with every cell set to `fill`.
+    pub fn push_row(&mut self, fill: T)
+    where
+        T: Clone,
+    {
+        self.cells.resize(self.cells.len() + self.width, fill);
+        self.height += 1;
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates over the rows from top to bottom. A grid with no columns has no cells, so this
+    /// yields nothing rather than panicking on a chunk size of zero.
+    pub fn rows(&self) -> ChunksExact<'_, T> {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    /// Iterates down the column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> StepBy<slice::Iter<'_, T>> {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// Iterates over every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| ((idx % width, idx / width), cell))
+    }
+
+    /// The positions above, left, right and below `(x, y)` which are in the grid.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, ORTHOGONAL)
+    }
+
+    /// The positions around `(x, y)`, including diagonals, which are in the grid.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, ALL_DIRECTIONS)
+    }
+
+    fn neighbours(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+            self.contains(x, y).then_some((x, y))
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(self.contains(x, y), "({x}, {y}) is outside the grid");
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(self.contains(x, y), "({x}, {y}) is outside the grid");
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Draws the grid with one row per line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse(input, "a digit", |_, b| {
+            b.is_ascii_digit().then_some(b - b'0')
+        })
+    }
+
+    #[test]
+    fn parse_test() {
+        let grid = digits("123\n456\n").unwrap();
+
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(6, grid[(2, 1)]);
+        assert_eq!(None, grid.get(3, 0));
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(vec![&2, &5], grid.column(1).collect::<Vec<_>>());
+        assert_eq!("123\n456", grid.to_string());
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let error = digits("123\n4x6\n").unwrap_err();
+        assert_eq!(
+            (2, 2, "x"),
+            (error.line, error.column, error.snippet.as_str())
+        );
+
+        let error = digits("123\n45\n").unwrap_err();
+        assert_eq!((2, 3), (error.line, error.column));
+        assert_eq!("a row 3 wide", error.expected);
+    }
+
+    #[test]
+    fn empty_test() {
+        assert!(digits("").is_err());
+
+        let grid = Grid::new(0, 3, 0u8);
+        assert_eq!(0, grid.rows().count());
+        assert_eq!("", grid.to_string());
+    }
+
+    #[test]
+    fn neighbours_test() {
+        let grid = Grid::new(3, 3, 0);
+
+        let corner: Vec<_> = grid.neighbours4(0, 0).collect();
+        assert_eq!(vec![(1, 0), (0, 1)], corner);
+        assert_eq!(4, grid.neighbours4(1, 1).count());
+        assert_eq!(3, grid.neighbours8(2, 2).count());
+        assert_eq!(8, grid.neighbours8(1, 1).count());
+    }
+}
@@ -0,0 +1,365 @@
+//! Normalisation of puzzle inputs before they reach the solvers.
+//!
+//! The solvers are written against inputs as they're downloaded: LF line endings, and a single
+//! newline at the end. Inputs which have been through a Windows editor can have a byte order
+//! mark, CRLF line endings, or no final newline, which would otherwise trip up the solvers that
+//! work directly on the bytes. [`normalise`] removes those differences, then checks the input has
+//! the [`Shape`] its solver requires.
+//!
+//! Inputs too large to hold in memory can be read a line at a time with [`for_each_line`], which
+//! normalises each line the same way.
+//!
+//! Parsers report malformed input with a [`ParseError`], which points at the offending text.
+
+use std::{
+    borrow::Cow,
+    error::Error,
+    fmt::{self, Display},
+    io::BufRead,
+    str::FromStr,
+};
+
+use color_eyre::eyre::{eyre, WrapErr};
+
+use crate::DayError;
+
+/// The layout a solver requires of its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// Any number of lines.
+    Lines,
+    /// A single line, such as a datastream.
+    Line,
+    /// Lines which are all the same length, such as a map.
+    Grid,
+}
+
+/// Strips any byte order mark, converts CRLF line endings to LF, and makes sure the input ends
+/// with exactly one newline.
+///
+/// Returns an error if the input is empty, has carriage returns outside of a line ending, or
+/// doesn't match the shape.
+pub fn normalise(input: &str, shape: Shape) -> Result<Cow<'_, str>, DayError> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+
+    let mut input = if input.contains('\r') {
+        let input = input.replace("\r\n", "\n");
+        if let Some(line) = input.lines().position(|l| l.contains('\r')) {
+            return Err(eyre!("Stray carriage return on line {}", line + 1));
+        }
+        Cow::Owned(input)
+    } else {
+        Cow::Borrowed(input)
+    };
+
+    let content_len = input.trim_end_matches('\n').len();
+    if content_len == 0 {
+        return Err(eyre!("Input is empty"));
+    }
+    if content_len + 1 != input.len() {
+        let mut trimmed = input.into_owned();
+        trimmed.truncate(content_len);
+        trimmed.push('\n');
+        input = Cow::Owned(trimmed);
+    }
+
+    match shape {
+        Shape::Lines => {}
+        Shape::Line => {
+            let num_lines = input.lines().count();
+            if num_lines != 1 {
+                return Err(eyre!("Expected a single line, found {}", num_lines));
+            }
+        }
+        Shape::Grid => {
+            let mut lines = input.lines();
+            let width = lines.next().map_or(0, str::len);
+            if let Some((idx, line)) = lines.enumerate().find(|(_, l)| l.len() != width) {
+                return Err(eyre!(
+                    "Line {} is {} wide, expected {}",
+                    idx + 2,
+                    line.len(),
+                    width
+                ));
+            }
+        }
+    }
+
+    Ok(input)
+}
+
+/// An error in the puzzle input, which shows where it was found when displayed:
+///
+/// ```text
+/// line 2, column 6: expected a number, found `x`
+///   |
+/// 2 | move x from 1 to 2
+///   |      ^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the error, starting from 1.
+    pub line: usize,
+    /// The column of the error in characters, starting from 1.
+    pub column: usize,
+    /// The text which was found instead of what was expected. Empty at the end of a line.
+    pub snippet: String,
+    /// A description of what should have been there.
+    pub expected: String,
+    source_line: String,
+}
+
+impl ParseError {
+    /// Creates an error pointing at `span`, which must be a slice of `input`.
+    pub fn at(input: &str, span: &str, expected: impl Into<String>) -> Self {
+        let offset = (span.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        debug_assert!(offset <= input.len(), "span is not part of the input");
+
+        Self::at_offset(input, offset.min(input.len()), span.len(), expected)
+    }
+
+    /// Creates an error pointing at `len` bytes of `input`, starting at the byte `offset`.
+    pub fn at_offset(input: &str, offset: usize, len: usize, expected: impl Into<String>) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |idx| offset + idx);
+        let mut snippet_end = (offset + len).min(line_end);
+        while !input.is_char_boundary(snippet_end) {
+            snippet_end += 1;
+        }
+
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[offset..snippet_end].to_owned(),
+            expected: expected.into(),
+            source_line: input[line_start..line_end].to_owned(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.snippet.is_empty() {
+            writeln!(f, "end of line")?;
+        } else {
+            writeln!(f, "`{}`", self.snippet)?;
+        }
+
+        let gutter = self.line.to_string().len();
+        let carets = self.snippet.chars().count().max(1);
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{:gutter$} | {:indent$}{}",
+            "",
+            "",
+            "^".repeat(carets),
+            indent = self.column - 1
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `span`, which must be a slice of `input`, pointing at it if it isn't a valid `T`.
+pub fn parse_at<T: FromStr>(input: &str, span: &str, expected: &str) -> Result<T, ParseError> {
+    span.parse()
+        .map_err(|_| ParseError::at(input, span, expected))
+}
+
+/// Reads `reader` a line at a time, and passes each line to `f` without its line ending. Only the
+/// current line is held in memory.
+///
+/// The lines are normalised as [`normalise`] would for [`Shape::Lines`]: a byte order mark and
+/// CRLF line endings are stripped, and blank lines at the end are skipped. The parsers can treat
+/// each line as the whole input, as a [`ParseError`] from `f` is moved to the line's number.
+pub fn for_each_line(
+    mut reader: impl BufRead,
+    mut f: impl FnMut(&str) -> Result<(), DayError>,
+) -> Result<(), DayError> {
+    let mut call = |line_num: usize, line: &str| {
+        f(line).map_err(|e| match e.downcast::<ParseError>() {
+            Ok(mut error) => {
+                error.line = line_num;
+                error.into()
+            }
+            Err(e) => e.wrap_err(format!("Line {line_num}")),
+        })
+    };
+
+    let mut buf = String::new();
+    let mut line_num = 0;
+    // Blank lines are held back until there's another line, in case they're at the end.
+    let mut blank_lines = 0;
+    let mut is_empty = true;
+    loop {
+        buf.clear();
+        let len = reader
+            .read_line(&mut buf)
+            .wrap_err("Failed to read the input")?;
+        if len == 0 {
+            break;
+        }
+        line_num += 1;
+
+        let mut line = buf.strip_suffix('\n').unwrap_or(&buf);
+        line = line.strip_suffix('\r').unwrap_or(line);
+        if line_num == 1 {
+            line = line.strip_prefix('\u{feff}').unwrap_or(line);
+        }
+        if line.contains('\r') {
+            return Err(eyre!("Stray carriage return on line {}", line_num));
+        }
+
+        if line.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+        for blank in line_num - blank_lines..line_num {
+            call(blank, "")?;
+        }
+        blank_lines = 0;
+
+        call(line_num, line)?;
+        is_empty = false;
+    }
+
+    if is_empty {
+        return Err(eyre!("Input is empty"));
+    }
+
+    Ok(())
+}
+
+/// Reads `line` and a newline `count` times, without holding them all in memory, for testing
+/// [`for_each_line`]'s callers on inputs too large to build as a string.
+#[cfg(test)]
+pub(crate) fn repeat_line(line: &'static str, count: usize) -> impl BufRead {
+    struct RepeatLine {
+        line: &'static [u8],
+        remaining: usize,
+    }
+
+    impl std::io::Read for RepeatLine {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = self.line.len() + 1;
+            let lines = (buf.len() / len).min(self.remaining);
+            for chunk in buf[..lines * len].chunks_exact_mut(len) {
+                chunk[..len - 1].copy_from_slice(self.line);
+                chunk[len - 1] = b'\n';
+            }
+            self.remaining -= lines;
+            Ok(lines * len)
+        }
+    }
+
+    std::io::BufReader::new(RepeatLine {
+        line: line.as_bytes(),
+        remaining: count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_test() {
+        let input = "1\n2\n\n3\n";
+        let actual = normalise(input, Shape::Lines).unwrap();
+
+        assert!(matches!(actual, Cow::Borrowed(_)));
+        assert_eq!(input, actual);
+    }
+
+    #[test]
+    fn windows_test() {
+        let expected = "1\n2\n\n3\n";
+        let actual = normalise("\u{feff}1\r\n2\r\n\r\n3", Shape::Lines).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn trailing_newlines_test() {
+        let expected = "abc\n";
+        let actual = normalise("abc\n\n\n", Shape::Line).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn invalid_test() {
+        assert!(normalise("", Shape::Lines).is_err());
+        assert!(normalise("\r\n\r\n", Shape::Lines).is_err());
+        assert!(normalise("1\r2\n", Shape::Lines).is_err());
+        assert!(normalise("abc\ndef\n", Shape::Line).is_err());
+        assert!(normalise("abc\nde\n", Shape::Grid).is_err());
+    }
+
+    #[test]
+    fn for_each_line_test() {
+        let mut lines = Vec::new();
+        for_each_line("\u{feff}1\r\n\r\n2\n\n\n".as_bytes(), |line| {
+            lines.push(line.to_owned());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(["1", "", "2"], lines.as_slice());
+
+        assert!(for_each_line("\r\n\n".as_bytes(), |_| Ok(())).is_err());
+        assert!(for_each_line("1\r2\n".as_bytes(), |_| Ok(())).is_err());
+
+        let error = for_each_line("abc\nabd\n".as_bytes(), |line| match line.find('d') {
+            Some(idx) => Err(ParseError::at(line, &line[idx..], "`c`").into()),
+            None => Ok(()),
+        })
+        .unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(2, error.line);
+        assert_eq!(3, error.column);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let input = "move 1 from 2 to 3\nmove x from 1 to 2\n";
+        let span = &input[24..25];
+        let error = ParseError::at(input, span, "a number");
+
+        assert_eq!(2, error.line);
+        assert_eq!(6, error.column);
+        assert_eq!("x", error.snippet);
+
+        let expected = "\
+line 2, column 6: expected a number, found `x`
+  |
+2 | move x from 1 to 2
+  |      ^";
+        assert_eq!(expected, error.to_string());
+    }
+
+    #[test]
+    fn parse_error_end_of_line_test() {
+        let input = "abc\n";
+        let error = ParseError::at(input, &input[3..3], "`d`");
+
+        assert_eq!(1, error.line);
+        assert_eq!(4, error.column);
+        assert!(error
+            .to_string()
+            .starts_with("line 1, column 4: expected `d`, found end of line"));
+    }
+}
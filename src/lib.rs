@@ -0,0 +1,35 @@
+//! Solutions for Advent of Code 2022.
+//!
+//! Each day is a module under [`days`], and they all expose the same API:
+//!
+//! * `Parsed`, the type the puzzle input is parsed into.
+//! * `parse(&str) -> Result<Parsed, DayError>`.
+//! * `part1(&Parsed)`, and `part2(&Parsed)` for days which have a second part. Parts which can
+//!   fail on input that parsed but has no solution return a `Result`.
+//!
+//! Days 1 to 4, 9, 10 and 25 also have `stream(impl BufRead)`, which solves both parts a line at
+//! a time, for inputs too large to read into memory.
+//!
+//! Each module also implements [`solution::Solution`] for its day, and has a `DAY` constant built
+//! from it for registering with the `aoc_lib` runner.
+//!
+//! The parsers expect input as it's downloaded, with LF line endings and a final newline. Inputs
+//! from elsewhere should go through [`input::normalise`] first, which the runner does for them.
+
+pub mod answers;
+pub mod budget;
+pub mod days;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod search;
+pub mod solution;
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOC: budget::CountingAlloc<aoc_lib::TracingAlloc> =
+    budget::CountingAlloc(aoc_lib::TracingAlloc);
+
+/// The error returned when a day's input couldn't be parsed.
+pub type DayError = color_eyre::Report;
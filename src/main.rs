@@ -1,14 +1,190 @@
-use aoc_lib::TracingAlloc;
-use color_eyre::Result;
+use std::{io, panic};
 
-mod days;
+use advent_of_code_2022::{
+    answers::AnswerStore,
+    days::{self, day01, day02, day03, day04, day09, day10, day25},
+    generate,
+    solution::Part,
+};
+use aoc_lib::TracingAlloc;
+use color_eyre::{eyre::eyre, Result};
 
 #[global_allocator]
 static ALLOC: TracingAlloc = TracingAlloc;
 
 fn main() -> Result<()> {
     color_eyre::install()?;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("--cross-check") => return cross_check(),
+        Some("--generate") => return generate(&args[1..]),
+        Some("--verify") => return verify(false),
+        Some("--record") => return verify(true),
+        Some("--stream") => return stream(&args[1..]),
+        _ => {}
+    }
+
     aoc_lib::run(&ALLOC, 2022, days::DAYS)?;
 
     Ok(())
 }
+
+/// Runs the alternative solvers on the real inputs, and reports any which disagree with the
+/// canonical parts.
+fn cross_check() -> Result<()> {
+    let mut num_mismatches = 0;
+    for check in days::CROSS_CHECKS {
+        let input = aoc_lib::input(check.day).open()?;
+        for mismatch in (check.run)(&input)? {
+            eprintln!("{mismatch}");
+            num_mismatches += 1;
+        }
+    }
+
+    if num_mismatches > 0 {
+        return Err(eyre!("{} variants disagreed", num_mismatches));
+    }
+
+    println!("All variants agree");
+    Ok(())
+}
+
+const YEAR: u16 = 2022;
+
+/// Where the known answers to the real inputs are kept. It's not checked in, as the inputs
+/// aren't either.
+const ANSWERS_PATH: &str = "answers.txt";
+
+/// Runs every day's parts and variants on the real inputs, and compares them with the known
+/// answers in [`ANSWERS_PATH`].
+///
+/// With `record`, answers to parts which aren't known yet are added to the store. Known answers
+/// are never replaced, so a wrong one has to be removed from the file by hand.
+fn verify(record: bool) -> Result<()> {
+    let mut store = AnswerStore::load(ANSWERS_PATH)?;
+    let mut num_failures = 0;
+    let mut num_unknown = 0;
+    let mut fail = |msg: String| {
+        eprintln!("{msg}");
+        num_failures += 1;
+    };
+
+    for solver in days::SOLVERS {
+        let day = solver.day;
+        let input = match aoc_lib::input(day).open() {
+            Ok(input) => input,
+            Err(e) => {
+                fail(format!("Day {day}: failed to open the input: {e}"));
+                continue;
+            }
+        };
+        // A day which panics is a failure like any other, rather than the end of the run.
+        let answers = panic::catch_unwind(|| {
+            (solver.normalise)(&input).and_then(|input| Ok(((solver.parts)(&input)?, input)))
+        });
+        let (answers, input) = match answers {
+            Ok(Ok(answers)) => answers,
+            Ok(Err(e)) => {
+                fail(format!("Day {day}: {e:?}"));
+                continue;
+            }
+            Err(_) => {
+                fail(format!("Day {day}: panicked"));
+                continue;
+            }
+        };
+
+        let parts = [(Part::One, Some(answers.part1)), (Part::Two, answers.part2)];
+        for (part, answer) in parts {
+            let Some(answer) = answer else { continue };
+            match store.get(YEAR, day, part) {
+                Some(known) if known == answer => {}
+                Some(known) => fail(format!(
+                    "Day {day} part {}: expected {known}, got {answer}",
+                    part_number(part)
+                )),
+                None if record => store.insert(YEAR, day, part, answer),
+                None => {
+                    println!("Day {day} part {}: no known answer", part_number(part));
+                    num_unknown += 1;
+                }
+            }
+        }
+
+        for variant in solver.variants {
+            let Some(known) = store.get(YEAR, day, variant.part) else {
+                continue;
+            };
+            match panic::catch_unwind(|| (variant.solve)(&input)) {
+                Ok(Ok(answer)) if answer == known => {}
+                Ok(Ok(answer)) => fail(format!(
+                    "Day {day} `{}`: expected {known}, got {answer}",
+                    variant.name
+                )),
+                Ok(Err(e)) => fail(format!("Day {day} `{}`: {e:?}", variant.name)),
+                Err(_) => fail(format!("Day {day} `{}`: panicked", variant.name)),
+            }
+        }
+    }
+
+    if record {
+        store.save(ANSWERS_PATH)?;
+    }
+    if num_failures > 0 {
+        return Err(eyre!("{} answers didn't match", num_failures));
+    }
+
+    if num_unknown > 0 {
+        println!("All known answers match, {num_unknown} unknown; run with --record to add them");
+    } else {
+        println!("All answers match");
+    }
+    Ok(())
+}
+
+fn part_number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+/// Solves the input on stdin a line at a time, for the days which can, so inputs larger than
+/// memory can be solved.
+fn stream(args: &[String]) -> Result<()> {
+    let [day] = args else {
+        return Err(eyre!("Usage: --stream <day>"));
+    };
+
+    let stdin = io::stdin().lock();
+    let (part1, part2) = match day.parse()? {
+        1 => day01::stream(stdin).map(|(a, b)| (a.to_string(), b.to_string()))?,
+        2 => day02::stream(stdin).map(|(a, b)| (a.to_string(), b.to_string()))?,
+        3 => day03::stream(stdin).map(|(a, b)| (a.to_string(), b.to_string()))?,
+        4 => day04::stream(stdin).map(|(a, b)| (a.to_string(), b.to_string()))?,
+        9 => day09::stream(stdin).map(|(a, b)| (a.to_string(), b.to_string()))?,
+        10 => day10::stream(stdin).map(|(a, b)| (a.to_string(), b))?,
+        25 => (day25::stream(stdin)?, String::new()),
+        day => return Err(eyre!("Day {} can't be streamed", day)),
+    };
+
+    println!("Part 1: {part1}");
+    if !part2.is_empty() {
+        println!("Part 2: {part2}");
+    }
+
+    Ok(())
+}
+
+/// Writes a generated input to stdout, from the day, size and seed given as arguments.
+fn generate(args: &[String]) -> Result<()> {
+    let [day, size, seed] = args else {
+        return Err(eyre!("Usage: --generate <day> <size> <seed>"));
+    };
+
+    let input = generate::generate(day.parse()?, size.parse()?, seed.parse()?)?;
+    print!("{input}");
+
+    Ok(())
+}
@@ -0,0 +1,328 @@
+//! Shortest path searches over graphs described by closures, for the maze days.
+//!
+//! Each search starts from any number of sources, and stops at the first node which satisfies
+//! `is_target`, so finding the nearest of several targets is a single search. The `neighbours`
+//! closure is given the cost of reaching the node as well as the node itself, for graphs which
+//! change as time passes, such as the blizzards on day 24.
+
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use crate::geometry::Coord;
+
+/// The cheapest path found by a search, from its source to its target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    /// Every node along the path, including the source and target.
+    pub nodes: Vec<N>,
+}
+
+/// Breadth-first search, for graphs where every step costs 1.
+pub fn bfs<N, I>(
+    sources: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N, usize) -> I,
+    is_target: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::new();
+    let (target, cost) = bfs_with(&mut parents, sources, neighbours, is_target)?;
+    let nodes = reconstruct(target, |n| parents[n]);
+
+    Some(Path { cost, nodes })
+}
+
+/// Breadth-first search which records the nodes it reaches in `visited`, for graphs where a
+/// [`HashMap`] of every node would be too slow. Returns the target reached and its cost, but not
+/// the path to it.
+pub fn bfs_with<N, I>(
+    visited: &mut impl Visited<N>,
+    sources: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N, usize) -> I,
+    mut is_target: impl FnMut(&N) -> bool,
+) -> Option<(N, usize)>
+where
+    N: Copy,
+    I: IntoIterator<Item = N>,
+{
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if visited.visit(source, None) {
+            queue.push_back((source, 0));
+        }
+    }
+
+    while let Some((node, cost)) = queue.pop_front() {
+        if is_target(&node) {
+            return Some((node, cost));
+        }
+
+        for next in neighbours(&node, cost) {
+            if visited.visit(next, Some(node)) {
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// The nodes a search has reached.
+pub trait Visited<N> {
+    /// Records that `node` was reached from `parent`, which is `None` for a source. Returns
+    /// `false` if it had already been reached, in which case the first parent is kept.
+    fn visit(&mut self, node: N, parent: Option<N>) -> bool;
+}
+
+/// Keeps the parent of every node, so the path can be read back.
+impl<N: Eq + Hash> Visited<N> for HashMap<N, Option<N>> {
+    fn visit(&mut self, node: N, parent: Option<N>) -> bool {
+        match self.entry(node) {
+            Entry::Vacant(entry) => {
+                entry.insert(parent);
+                true
+            }
+            Entry::Occupied(_) => false,
+        }
+    }
+}
+
+/// A flag for each node, for graphs where every node has a small index.
+pub struct DenseVisited<F> {
+    seen: Vec<bool>,
+    index: F,
+}
+
+impl<F> DenseVisited<F> {
+    /// `index` must give every node a different index, below `len`.
+    pub fn new(len: usize, index: F) -> Self {
+        Self {
+            seen: vec![false; len],
+            index,
+        }
+    }
+}
+
+impl<N, F: Fn(&N) -> usize> Visited<N> for DenseVisited<F> {
+    fn visit(&mut self, node: N, _: Option<N>) -> bool {
+        let seen = &mut self.seen[(self.index)(&node)];
+        !std::mem::replace(seen, true)
+    }
+}
+
+/// Dijkstra's algorithm, where `cost` gives the cost of moving between two neighbours.
+pub fn dijkstra<N, C, I>(
+    sources: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N, C) -> I,
+    cost: impl FnMut(&N, &N) -> C,
+    is_target: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Copy + Eq + Hash,
+    C: Coord,
+    I: IntoIterator<Item = N>,
+{
+    astar(sources, neighbours, cost, |_| C::ZERO, is_target)
+}
+
+/// A* search. The `heuristic` estimates the remaining cost to the nearest target, and must never
+/// overestimate it, or the path found may not be the cheapest.
+pub fn astar<N, C, I>(
+    sources: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N, C) -> I,
+    mut cost: impl FnMut(&N, &N) -> C,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_target: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Copy + Eq + Hash,
+    C: Coord,
+    I: IntoIterator<Item = N>,
+{
+    let mut best = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    for source in sources {
+        best.insert(source, (C::ZERO, None));
+        queue.push(Candidate {
+            estimate: heuristic(&source),
+            cost: C::ZERO,
+            node: source,
+        });
+    }
+
+    while let Some(candidate) = queue.pop() {
+        let (so_far, node) = (candidate.cost, candidate.node);
+        if so_far > best[&node].0 {
+            // We've since found a cheaper way here.
+            continue;
+        }
+        if is_target(&node) {
+            let nodes = reconstruct(node, |n| best[n].1);
+            return Some(Path {
+                cost: so_far,
+                nodes,
+            });
+        }
+
+        for next in neighbours(&node, so_far) {
+            let next_cost = so_far + cost(&node, &next);
+            if best.get(&next).is_some_and(|&(c, _)| c <= next_cost) {
+                continue;
+            }
+
+            best.insert(next, (next_cost, Some(node)));
+            queue.push(Candidate {
+                estimate: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+
+    None
+}
+
+fn reconstruct<N: Copy>(target: N, parent: impl Fn(&N) -> Option<N>) -> Vec<N> {
+    let mut nodes = vec![target];
+    while let Some(prev) = parent(nodes.last().unwrap()) {
+        nodes.push(prev);
+    }
+    nodes.reverse();
+
+    nodes
+}
+
+/// A node waiting in the A* queue. Ordered so the lowest estimate is popped first, which
+/// `BinaryHeap` being a max-heap means reversing the comparison.
+struct Candidate<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Candidate<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| other.cost.cmp(&self.cost))
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Candidate<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Candidate<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Candidate<N, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E
+";
+
+    type Pos = (usize, usize);
+
+    fn maze() -> Grid<u8> {
+        Grid::parse(MAZE, "a maze cell", |_, b| Some(b)).unwrap()
+    }
+
+    fn open(grid: &Grid<u8>, (x, y): Pos) -> impl Iterator<Item = Pos> + '_ {
+        grid.neighbours4(x, y).filter(|&p| grid[p] != b'#')
+    }
+
+    fn check_path(grid: &Grid<u8>, path: &Path<Pos, usize>) {
+        assert_eq!(path.cost + 1, path.nodes.len());
+        for pair in path.nodes.windows(2) {
+            assert!(open(grid, pair[0]).any(|p| p == pair[1]));
+        }
+    }
+
+    #[test]
+    fn bfs_test() {
+        let grid = maze();
+        let path = bfs([(0, 0)], |&p, _| open(&grid, p), |&p| p == (7, 4)).unwrap();
+
+        assert_eq!(15, path.cost);
+        assert_eq!((0, 0), path.nodes[0]);
+        assert_eq!((7, 4), *path.nodes.last().unwrap());
+        check_path(&grid, &path);
+    }
+
+    #[test]
+    fn astar_test() {
+        let grid = maze();
+        let target = (7, 4);
+        let manhattan = |&(x, y): &Pos| x.abs_diff(target.0) + y.abs_diff(target.1);
+
+        let path = astar(
+            [(0, 0)],
+            |&p, _| open(&grid, p),
+            |_, _| 1,
+            manhattan,
+            |&p| p == target,
+        )
+        .unwrap();
+        assert_eq!(15, path.cost);
+        check_path(&grid, &path);
+
+        let path = dijkstra([(0, 0)], |&p, _| open(&grid, p), |_, _| 1, |&p| p == target);
+        assert_eq!(Some(15), path.map(|p| p.cost));
+    }
+
+    #[test]
+    fn multiple_sources_test() {
+        let grid = maze();
+
+        // The nearest target is found, from whichever source is closest to it.
+        let path = bfs(
+            [(0, 0), (7, 0)],
+            |&p, _| open(&grid, p),
+            |&p| p == (4, 2) || p == (7, 4),
+        )
+        .unwrap();
+        assert_eq!(4, path.cost);
+        assert_eq!((7, 0), path.nodes[0]);
+        assert_eq!((7, 4), path.nodes[4]);
+        check_path(&grid, &path);
+
+        assert!(bfs([(0, 0)], |&p, _| open(&grid, p), |&p| p == (3, 0)).is_none());
+    }
+
+    #[test]
+    fn dense_visited_test() {
+        let grid = maze();
+        let mut visited = DenseVisited::new(grid.width() * grid.height(), |&(x, y): &Pos| {
+            y * grid.width() + x
+        });
+
+        let found = bfs_with(
+            &mut visited,
+            [(0, 0)],
+            |&p, _| open(&grid, p),
+            |&p| p == (7, 4),
+        );
+        assert_eq!(Some(((7, 4), 15)), found);
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+    panic,
+};
+
+use aoc_lib::{Bench, BenchResult, Day, ParseResult, UserError};
+use color_eyre::{eyre::eyre, Report};
+
+use crate::{input::Shape, DayError};
+
+/// The signature `aoc_lib` uses to run each part and variant.
+pub type Runner = fn(&str, Bench) -> BenchResult;
+
+/// A single day's puzzle, which [`day`] turns into an `aoc_lib::Day`.
+pub trait Solution {
+    const DAY: u8;
+    const NAME: &'static str;
+
+    /// Day 25 only has the one part. Days without a part 2 clear this, and still implement
+    /// [`Solution::part2`], returning an error, so a day can't lose its part 2 by forgetting it.
+    const HAS_PART_2: bool = true;
+
+    /// Benchmark part 2 with `bench_alt`, for answers which are drawn rather than a number.
+    const PART_2_ALT: bool = false;
+
+    /// The layout the input must have once it's been normalised.
+    const INPUT: Shape = Shape::Lines;
+
+    /// Extra variants to benchmark, such as parsing on its own, or solvers that work directly
+    /// on the input instead of going through [`Solution::Parsed`].
+    const OTHER: &'static [(&'static str, Runner)] = &[];
+
+    /// Alternative solvers from [`Solution::OTHER`] which should give the same answer as one of
+    /// the parts, for [`cross_check`].
+    const VARIANTS: &'static [Variant] = &[];
+
+    type Parsed<'a>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, DayError>;
+    fn part1(data: &Self::Parsed<'_>) -> Result<Self::Answer1, DayError>;
+    fn part2(data: &Self::Parsed<'_>) -> Result<Self::Answer2, DayError>;
+
+    /// Solves part 1 with `params` in place of values the puzzle text gives alongside the input,
+    /// such as the row day 15 asks about, which differ between the examples and the real input.
+    /// Only days with such values override it.
+    fn part1_with(_data: &Self::Parsed<'_>, _params: &Params) -> Result<Self::Answer1, DayError> {
+        Err(eyre!("Day {} takes no parameters", Self::DAY))
+    }
+
+    /// As [`Solution::part1_with`], for part 2.
+    fn part2_with(_data: &Self::Parsed<'_>, _params: &Params) -> Result<Self::Answer2, DayError> {
+        Err(eyre!("Day {} takes no parameters", Self::DAY))
+    }
+}
+
+/// Named values for [`Solution::part1_with`] and [`Solution::part2_with`].
+pub type Params = [(&'static str, i64)];
+
+/// Looks up the parameter called `name`.
+pub fn param<T: TryFrom<i64>>(params: &Params, name: &str) -> Result<T, DayError> {
+    let &(_, value) = params
+        .iter()
+        .find(|(n, _)| *n == name)
+        .ok_or_else(|| eyre!("Missing parameter `{}`", name))?;
+    T::try_from(value).map_err(|_| eyre!("Parameter `{}` is out of range: {}", name, value))
+}
+
+pub const fn day<S: Solution>() -> Day {
+    let part_2: Runner = if S::PART_2_ALT {
+        run_part2_alt::<S>
+    } else {
+        run_part2::<S>
+    };
+
+    Day {
+        day: S::DAY,
+        name: S::NAME,
+        part_1: run_part1::<S>,
+        part_2: if S::HAS_PART_2 { Some(part_2) } else { None },
+        other: S::OTHER,
+    }
+}
+
+/// Normalises the input for the day's solvers, as every runner does before benchmarking them.
+pub fn normalise<S: Solution>(input: &str) -> Result<Cow<'_, str>, DayError> {
+    crate::input::normalise(input, S::INPUT)
+}
+
+fn run_part1<S: Solution>(input: &str, b: Bench) -> BenchResult {
+    let input = normalise::<S>(input).map_err(UserError)?;
+    let data = S::parse(&input).map_err(UserError)?;
+    b.bench(|| S::part1(&data))
+}
+
+fn run_part2<S: Solution>(input: &str, b: Bench) -> BenchResult {
+    let input = normalise::<S>(input).map_err(UserError)?;
+    let data = S::parse(&input).map_err(UserError)?;
+    b.bench(|| S::part2(&data))
+}
+
+fn run_part2_alt<S: Solution>(input: &str, b: Bench) -> BenchResult {
+    let input = normalise::<S>(input).map_err(UserError)?;
+    let data = S::parse(&input).map_err(UserError)?;
+    b.bench_alt(|| S::part2(&data))
+}
+
+/// Benchmarks [`Solution::parse`] on its own, for registering in [`Solution::OTHER`].
+pub fn run_parse<S: Solution>(input: &str, b: Bench) -> BenchResult {
+    let input = normalise::<S>(input).map_err(UserError)?;
+    b.bench(|| {
+        let data = S::parse(&input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// An alternative solver for one of the parts, which takes the normalised input and returns the
+/// answer as it would be displayed.
+#[derive(Clone, Copy)]
+pub struct Variant {
+    pub name: &'static str,
+    pub part: Part,
+    pub solve: fn(&str) -> Result<String, DayError>,
+}
+
+/// A variant which gave a different answer to the part it's an alternative for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: u8,
+    pub variant: &'static str,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Day {} `{}`: expected {}, got {}",
+            self.day, self.variant, self.expected, self.actual
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct CrossCheck {
+    pub day: u8,
+    pub run: fn(&str) -> Result<Vec<Mismatch>, DayError>,
+}
+
+pub const fn cross_check<S: Solution>() -> CrossCheck {
+    CrossCheck {
+        day: S::DAY,
+        run: run_cross_check::<S>,
+    }
+}
+
+/// Runs each of the day's variants on the input, and compares them with the canonical parts.
+///
+/// Returns an error if the canonical solution fails, while a variant which fails or panics is
+/// reported as a mismatch.
+fn run_cross_check<S: Solution>(input: &str) -> Result<Vec<Mismatch>, DayError> {
+    let input = normalise::<S>(input)?;
+    let Answers { part1, part2 } = run_parts::<S>(&input)?;
+
+    let mut mismatches = Vec::new();
+    for variant in S::VARIANTS {
+        let expected = match variant.part {
+            Part::One => &part1,
+            Part::Two => part2
+                .as_ref()
+                .ok_or_else(|| eyre!("Variant `{}` checks a missing part 2", variant.name))?,
+        };
+
+        let actual = match panic::catch_unwind(|| (variant.solve)(&input)) {
+            Ok(Ok(answer)) => answer,
+            Ok(Err(e)) => format!("error: {e}"),
+            Err(_) => "a panic".to_owned(),
+        };
+
+        if &actual != expected {
+            mismatches.push(Mismatch {
+                day: S::DAY,
+                variant: variant.name,
+                expected: expected.clone(),
+                actual,
+            });
+        }
+    }
+
+    Ok(mismatches)
+}
+
+/// A day's solvers, for running outside of the benchmarks.
+#[derive(Clone, Copy)]
+pub struct Solver {
+    pub day: u8,
+    /// Normalises the input for the day, as [`Solver::parts`] and the variants expect it.
+    pub normalise: fn(&str) -> Result<Cow<'_, str>, DayError>,
+    /// Parses the input and solves both parts.
+    pub parts: fn(&str) -> Result<Answers, DayError>,
+    /// As [`Solver::parts`], with the day's parameters given rather than those of the real input.
+    pub parts_with: fn(&str, &Params) -> Result<Answers, DayError>,
+    pub variants: &'static [Variant],
+}
+
+pub const fn solver<S: Solution>() -> Solver {
+    Solver {
+        day: S::DAY,
+        normalise: normalise::<S>,
+        parts: run_parts::<S>,
+        parts_with: run_parts_with::<S>,
+        variants: S::VARIANTS,
+    }
+}
+
+/// The answers to a day's parts, as they would be displayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: String,
+    /// `None` for day 25, which only has the one part.
+    pub part2: Option<String>,
+}
+
+fn run_parts<S: Solution>(input: &str) -> Result<Answers, DayError> {
+    let data = S::parse(input)?;
+    let part1 = S::part1(&data)?.to_string();
+    let part2 = if S::HAS_PART_2 {
+        Some(S::part2(&data)?.to_string())
+    } else {
+        None
+    };
+
+    Ok(Answers { part1, part2 })
+}
+
+fn run_parts_with<S: Solution>(input: &str, params: &Params) -> Result<Answers, DayError> {
+    let data = S::parse(input)?;
+    let part1 = S::part1_with(&data, params)?.to_string();
+    let part2 = if S::HAS_PART_2 {
+        Some(S::part2_with(&data, params)?.to_string())
+    } else {
+        None
+    };
+
+    Ok(Answers { part1, part2 })
+}
@@ -0,0 +1,15 @@
+use advent_of_code_2022::days::day13;
+use aoc_lib::Example;
+
+#[test]
+fn day13_public_api() {
+    let data = aoc_lib::input(day13::DAY.day)
+        .example(Example::Part1, 1)
+        .open()
+        .unwrap();
+
+    let packets = day13::parse(&data).unwrap();
+
+    assert_eq!(13, day13::part1(&packets));
+    assert_eq!(140, day13::part2(&packets));
+}
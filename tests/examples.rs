@@ -0,0 +1,88 @@
+//! Checks the answers to the puzzle examples listed in `examples.txt`. The build script turns
+//! each line into a test which calls [`check`].
+
+use advent_of_code_2022::{
+    days,
+    solution::{Params, Part},
+};
+use aoc_lib::Example;
+
+struct Entry {
+    line: usize,
+    day: u8,
+    example: Example,
+    index: u8,
+    part1: Option<&'static str>,
+    part2: Option<&'static str>,
+    variant: Option<&'static str>,
+    /// Which line of the example to use, for files like day 6's which list several inputs
+    /// each followed by `-` and their answers.
+    case: Option<usize>,
+    params: &'static Params,
+}
+
+fn check(entry: &Entry) {
+    let line = entry.line;
+    let solver = days::SOLVERS
+        .iter()
+        .find(|s| s.day == entry.day)
+        .unwrap_or_else(|| panic!("line {line}: there's no day {}", entry.day));
+
+    let input = aoc_lib::input(entry.day)
+        .example(entry.example, entry.index)
+        .open()
+        .unwrap();
+    let input = match entry.case {
+        Some(case) => {
+            let text = input
+                .lines()
+                .nth(case - 1)
+                .unwrap_or_else(|| panic!("line {line}: the example has no case {case}"));
+            text.split_once('-')
+                .map_or(text, |(input, _)| input)
+                .to_owned()
+        }
+        None => input,
+    };
+    let input = (solver.normalise)(&input).unwrap();
+
+    let Some(name) = entry.variant else {
+        let answers = if entry.params.is_empty() {
+            (solver.parts)(&input).unwrap()
+        } else {
+            (solver.parts_with)(&input, entry.params).unwrap()
+        };
+        if let Some(expected) = entry.part1 {
+            assert_eq!(expected, answers.part1, "line {line}: part 1");
+        }
+        if let Some(expected) = entry.part2 {
+            assert_eq!(
+                Some(expected),
+                answers.part2.as_deref(),
+                "line {line}: part 2"
+            );
+        }
+        return;
+    };
+
+    assert!(
+        entry.params.is_empty(),
+        "line {line}: variants don't take parameters"
+    );
+    let variant = solver
+        .variants
+        .iter()
+        .find(|v| v.name == name)
+        .unwrap_or_else(|| panic!("line {line}: day {} has no variant `{name}`", entry.day));
+    let expected = match variant.part {
+        Part::One => entry.part1,
+        Part::Two => entry.part2,
+    };
+    let expected =
+        expected.unwrap_or_else(|| panic!("line {line}: no answer for the part `{name}` solves"));
+
+    let actual = (variant.solve)(&input).unwrap();
+    assert_eq!(expected, actual, "line {line}: `{name}`");
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));